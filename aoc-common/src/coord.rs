@@ -0,0 +1,59 @@
+use std::ops::{Add, Sub};
+
+// a point on a 2D grid - the days disagree about which integer type to use
+// (some index into vectors, some go negative, day 24 packs them into u8s)
+// so leave that up to them
+#[derive(Copy, Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Coord<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Coord<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn manhattan_distance_to(&self, other: &Coord<T>) -> T {
+        // work out the differences this way round so it's fine for unsigned types too
+        let dx = if self.x > other.x {
+            self.x - other.x
+        } else {
+            other.x - self.x
+        };
+        let dy = if self.y > other.y {
+            self.y - other.y
+        } else {
+            other.y - self.y
+        };
+        dx + dy
+    }
+}
+
+impl<T> From<(T, T)> for Coord<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add<(T, T)> for Coord<T> {
+    type Output = Coord<T>;
+
+    fn add(self, (other_x, other_y): (T, T)) -> Self::Output {
+        Self::Output {
+            x: self.x + other_x,
+            y: self.y + other_y,
+        }
+    }
+}
+
+#[test]
+fn test_manhattan_distance() {
+    let a: Coord<i32> = (2, 18).into();
+    let b: Coord<i32> = (-2, 15).into();
+    assert_eq!(a.manhattan_distance_to(&b), 7);
+    assert_eq!(b.manhattan_distance_to(&a), 7);
+
+    let c: Coord<u8> = (1, 5).into();
+    let d: Coord<u8> = (4, 2).into();
+    assert_eq!(c.manhattan_distance_to(&d), 6);
+}
@@ -0,0 +1,147 @@
+use std::{collections::HashMap, hash::Hash};
+
+// a stretch of steps that repeats forever: the state at `start + period` is
+// the same as the one at `start`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+// remembers the state at each step (numbered from 0), along with something
+// measured there - like the height of a tower - and spots the first time a state
+// comes round again. after that, the measurement at any later step can be worked
+// out without getting there: each time round the cycle adds the same amount
+pub struct CycleFinder<K> {
+    seen: HashMap<K, usize>,
+    metrics: Vec<i64>,
+    cycle: Option<Cycle>,
+}
+
+impl<K: Eq + Hash> CycleFinder<K> {
+    pub fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+            metrics: Vec::new(),
+            cycle: None,
+        }
+    }
+
+    // how many steps have been recorded
+    pub fn len(&self) -> usize {
+        self.metrics.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+
+    pub fn cycle(&self) -> Option<Cycle> {
+        self.cycle
+    }
+
+    // record the next step's state. returns the cycle, once there is one -
+    // nothing more needs recording after that
+    pub fn record(&mut self, key: K, metric: i64) -> Option<Cycle> {
+        if self.cycle.is_none() {
+            let step = self.metrics.len();
+            self.metrics.push(metric);
+            if let Some(&start) = self.seen.get(&key) {
+                self.cycle = Some(Cycle {
+                    start,
+                    period: step - start,
+                });
+            } else {
+                self.seen.insert(key, step);
+            }
+        }
+        self.cycle
+    }
+
+    // the metric at this step - either because it's been recorded, or because
+    // it's on a cycle. None if neither's true (yet)
+    pub fn metric_at(&self, step: usize) -> Option<i64> {
+        if let Some(&metric) = self.metrics.get(step) {
+            return Some(metric);
+        }
+
+        let Cycle { start, period } = self.cycle?;
+        let laps = ((step - start) / period) as i64;
+        let offset = (step - start) % period;
+        let gained_per_lap = self.metrics[start + period] - self.metrics[start];
+        Some(self.metrics[start + offset] + laps * gained_per_lap)
+    }
+}
+
+impl<K: Eq + Hash> Default for CycleFinder<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the metric at `target`, taking steps (with `next`, which gives the state it
+// ends up in and the metric there) until either it gets there or it finds a cycle
+pub fn metric_at<K: Eq + Hash>(target: usize, mut next: impl FnMut() -> (K, i64)) -> i64 {
+    let mut finder = CycleFinder::new();
+    loop {
+        if let Some(metric) = finder.metric_at(target) {
+            return metric;
+        }
+        let (key, metric) = next();
+        finder.record(key, metric);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 3 -> 0 -> 1 -> 2 -> 5 -> 6 -> 7 -> 4 -> 5 -> ..., adding the state to a running total
+    fn sequence() -> impl FnMut() -> (u64, i64) {
+        let mut state = 3;
+        let mut total = 0;
+        move || {
+            state = match state {
+                2 => 5,
+                3 => 0,
+                7 => 4,
+                s => s + 1,
+            };
+            total += state as i64;
+            (state, total)
+        }
+    }
+
+    #[test]
+    fn test_cycle_finder() {
+        let mut next = sequence();
+        let mut finder = CycleFinder::new();
+        let cycle = (0..20).find_map(|_| {
+            let (key, metric) = next();
+            finder.record(key, metric)
+        });
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 3,
+                period: 4
+            })
+        );
+        assert_eq!(finder.len(), 8);
+        assert_eq!(finder.metric_at(2), Some(3));
+    }
+
+    #[test]
+    fn test_metric_at() {
+        // the same as just running it all the way
+        for target in [0, 5, 7, 8, 9, 50, 1001] {
+            let mut next = sequence();
+            let expected = (0..=target).map(|_| next().1).last().unwrap();
+            assert_eq!(metric_at(target, sequence()), expected, "step {target}");
+        }
+
+        // and it doesn't need to get anywhere near
+        let total = metric_at(1_000_000_000_000, sequence());
+        assert_eq!(total, 5_499_999_999_992);
+    }
+}
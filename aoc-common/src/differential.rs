@@ -0,0 +1,219 @@
+use std::{
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::rng::Rng;
+
+// checks a solver that takes clever shortcuts against a slow one that's obviously
+// right, on lots of small random inputs. when they disagree (or either panics),
+// the input's shrunk down to the smallest case that still shows the problem, and
+// that's what gets reported
+pub trait Differential {
+    type Input: Clone + Debug;
+    type Output: PartialEq + Debug;
+
+    fn generate(rng: &mut Rng) -> Self::Input;
+
+    // smaller versions of the input to try, most drastic first. anything
+    // returned here has to still be a valid input
+    fn shrink(_input: &Self::Input) -> Vec<Self::Input> {
+        Vec::new()
+    }
+
+    fn reference(input: &Self::Input) -> Self::Output;
+    fn optimised(input: &Self::Input) -> Self::Output;
+}
+
+// the seed can be changed (to look further afield) with DIFFERENTIAL_SEED
+const DEFAULT_SEED: u64 = 2022;
+
+// what each solver came up with, when they don't agree
+struct Disagreement<O> {
+    reference: Result<O, String>,
+    optimised: Result<O, String>,
+}
+
+// a panic counts as an answer too - just a wrong one
+fn outcome<O>(solver: impl FnOnce() -> O) -> Result<O, String> {
+    panic::catch_unwind(AssertUnwindSafe(solver)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "(no message)".to_string())
+    })
+}
+
+fn disagreement<D: Differential>(input: &D::Input) -> Option<Disagreement<D::Output>> {
+    let reference = outcome(|| D::reference(input));
+    let optimised = outcome(|| D::optimised(input));
+    match (&reference, &optimised) {
+        (Ok(r), Ok(o)) if r == o => None,
+        _ => Some(Disagreement {
+            reference,
+            optimised,
+        }),
+    }
+}
+
+// keep taking the first smaller input that still fails, until none of them do
+fn shrink<D: Differential>(mut input: D::Input) -> D::Input {
+    // a shrinker that goes round in circles shouldn't hang the tests
+    for _ in 0..10_000 {
+        match D::shrink(&input)
+            .into_iter()
+            .find(|candidate| disagreement::<D>(candidate).is_some())
+        {
+            Some(smaller) => input = smaller,
+            None => break,
+        }
+    }
+    input
+}
+
+// run `cases` random inputs through both solvers, and panic with the smallest
+// input they disagree on, if there is one
+pub fn check<D: Differential>(cases: usize) {
+    let seed = std::env::var("DIFFERENTIAL_SEED")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_SEED);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let input = D::generate(&mut rng);
+        if disagreement::<D>(&input).is_none() {
+            continue;
+        }
+
+        let smallest = shrink::<D>(input);
+        let Disagreement {
+            reference,
+            optimised,
+        } = disagreement::<D>(&smallest).expect("shrinking only keeps failing inputs");
+        panic!(
+            "{} found a difference (seed {seed}, case {case})\ninput: {smallest:#?}\nreference: {reference:?}\noptimised: {optimised:?}",
+            std::any::type_name::<D>()
+        );
+    }
+}
+
+// the usual ways to make a list smaller: drop half of it, or just one item
+pub fn shrink_vec<T: Clone>(items: &[T], min_len: usize) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    // (halving one item off is the same as dropping it, below)
+    let half = items.len() / 2;
+    if half > 1 && items.len() - half >= min_len {
+        smaller.push(items[half..].to_vec());
+        smaller.push(items[..items.len() - half].to_vec());
+    }
+    if items.len() > min_len {
+        for i in 0..items.len() {
+            let mut fewer = items.to_vec();
+            fewer.remove(i);
+            smaller.push(fewer);
+        }
+    }
+    smaller
+}
+
+// numbers closer to zero
+pub fn shrink_number(n: i64) -> Vec<i64> {
+    let mut smaller = vec![0, n / 2, n - n.signum()];
+    smaller.dedup();
+    smaller.retain(|&m| m != n);
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // adds up a list - but the "optimised" version forgets about anything over 5
+    struct Sum;
+
+    impl Differential for Sum {
+        type Input = Vec<i64>;
+        type Output = i64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            (0..rng.below(20)).map(|_| rng.range(0..=9)).collect()
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            let mut smaller = shrink_vec(input, 0);
+            for (i, &n) in input.iter().enumerate() {
+                smaller.extend(shrink_number(n).into_iter().map(|m| {
+                    let mut changed = input.clone();
+                    changed[i] = m;
+                    changed
+                }));
+            }
+            smaller
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            input.iter().sum()
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            input.iter().filter(|&&n| n <= 5).sum()
+        }
+    }
+
+    // and one that's only wrong by panicking
+    struct Empty;
+
+    impl Differential for Empty {
+        type Input = Vec<i64>;
+        type Output = i64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            (0..rng.below(3)).map(|_| rng.range(0..=9)).collect()
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            shrink_vec(input, 0)
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            input.iter().copied().max().unwrap_or(0)
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            *input.iter().max().expect("there's always something")
+        }
+    }
+
+    fn failure(check: fn(usize)) -> String {
+        let payload = panic::catch_unwind(|| check(100)).unwrap_err();
+        payload.downcast_ref::<String>().unwrap().clone()
+    }
+
+    #[test]
+    fn test_shrinks_to_smallest_failure() {
+        let message = failure(check::<Sum>);
+        assert!(
+            message.contains("input: [\n    6,\n]"),
+            "didn't shrink all the way: {message}"
+        );
+        assert!(message.contains("reference: Ok(6)\noptimised: Ok(0)"));
+
+        let message = failure(check::<Empty>);
+        assert!(message.contains("input: []"), "{message}");
+        assert!(message.contains("optimised: Err(\"there's always something\")"));
+    }
+
+    #[test]
+    fn test_shrink_helpers() {
+        assert_eq!(
+            shrink_vec(&[1, 2, 3], 2),
+            [vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert_eq!(shrink_vec(&[1], 1), Vec::<Vec<i32>>::new());
+        assert_eq!(shrink_number(10), [0, 5, 9]);
+        assert_eq!(shrink_number(-1), [0]);
+        assert_eq!(shrink_number(0), Vec::<i64>::new());
+    }
+}
@@ -0,0 +1,351 @@
+//! Puzzle examples kept as fixture files, with a test generated for each one.
+//!
+//! Each day keeps its examples in `examples/*.toml`, which look like:
+//!
+//! ```toml
+//! part1 = 24000
+//! part2 = 45000
+//! input = '''
+//! 1000
+//! 2000
+//! '''
+//! ```
+//!
+//! Either answer can be left out, and `ignore_part1`/`ignore_part2` give a reason
+//! to skip that part's test. `ignore_whitespace` does the same for just the
+//! checks with the input saved differently (see below). Examples that use different numbers to the real
+//! puzzle can change the day's parameters, like `params = { row = 10 }`.
+//!
+//! Each answer is also checked with the input saved in other ways - windows line
+//! endings, trailing spaces, no final newline - which shouldn't change anything.
+//!
+//! The day's build script calls [`generate_tests`], and
+//! [`example_tests!`](crate::example_tests) pulls the generated tests in.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{params, Overrides, Part, Solution};
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Example {
+    pub input: String,
+    #[serde(default, deserialize_with = "answer")]
+    pub part1: Option<String>,
+    #[serde(default, deserialize_with = "answer")]
+    pub part2: Option<String>,
+    pub ignore_part1: Option<String>,
+    pub ignore_part2: Option<String>,
+    pub ignore_whitespace: Option<String>,
+    #[serde(default)]
+    pub params: Overrides,
+}
+
+// most answers are numbers, so let them be written without quotes
+fn answer<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Answer {
+        Number(i64),
+        Text(String),
+    }
+
+    Ok(match Answer::deserialize(deserializer)? {
+        Answer::Number(n) => Some(n.to_string()),
+        Answer::Text(s) => Some(s),
+    })
+}
+
+impl Example {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+
+    fn answer(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part1.as_deref(),
+            Part::Two => self.part2.as_deref(),
+        }
+    }
+
+    fn ignore(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.ignore_part1.as_deref(),
+            Part::Two => self.ignore_part2.as_deref(),
+        }
+    }
+
+    // the whitespace checks are skipped along with the part they're for
+    fn ignore_whitespace(&self, part: Part) -> Option<&str> {
+        self.ignore(part).or(self.ignore_whitespace.as_deref())
+    }
+}
+
+// check that a solution gets the expected answer for an example. the
+// parameters to change are given as TOML, the same as in the example file
+pub fn check<S: Solution>(part: Part, input: &str, expected: &str, overrides: &str) {
+    let overrides = overrides
+        .parse::<Overrides>()
+        .unwrap_or_else(|e| panic!("couldn't read the example's parameters: {e}"));
+    let params = params::with_overrides::<S::Params>(&overrides)
+        .unwrap_or_else(|e| panic!("the example's parameters don't fit: {e}"));
+
+    let answer = match part {
+        Part::One => {
+            S::parse_part1(input).map(|parsed| S::solve_part1(&parsed, &params).to_string())
+        }
+        Part::Two => {
+            S::parse_part2(input).map(|parsed| S::solve_part2(&parsed, &params).to_string())
+        }
+    };
+    match answer {
+        Ok(answer) => assert_eq!(answer, expected, "wrong answer for part {part}"),
+        Err(e) => panic!("couldn't parse the example: {e}"),
+    }
+}
+
+// the same input saved in the different ways people (and editors) save things
+pub fn whitespace_variants(input: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("windows line endings", input.replace('\n', "\r\n")),
+        (
+            "trailing spaces",
+            input.lines().map(|line| format!("{line}  \n")).collect(),
+        ),
+        ("no final newline", input.trim_end_matches('\n').to_string()),
+        ("extra blank lines", format!("\n{input}\n\n")),
+    ]
+}
+
+// check that an example's answer doesn't change however it's saved
+pub fn check_whitespace<S: Solution>(part: Part, input: &str, expected: &str, overrides: &str) {
+    for (variant, input) in whitespace_variants(input) {
+        let checked = std::panic::catch_unwind(|| check::<S>(part, &input, expected, overrides));
+        if let Err(payload) = checked {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .unwrap_or_default();
+            panic!("with {variant}: {message}");
+        }
+    }
+}
+
+// turn the name of a fixture file into something that works as part of a function name
+fn test_name(stem: &str) -> String {
+    stem.chars()
+        .map(|c| match c {
+            'a'..='z' | '0'..='9' => c,
+            'A'..='Z' => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+// the source for a test of each part of each example that has an answer
+pub fn render_tests(examples: &[(String, Example)]) -> String {
+    let mut code = String::new();
+    for (name, example) in examples {
+        for part in Part::both() {
+            let Some(expected) = example.answer(part) else {
+                continue;
+            };
+
+            // and the same again, saved a few different ways
+            let checks = [
+                ("", "check", example.ignore(part)),
+                (
+                    "_whitespace",
+                    "check_whitespace",
+                    example.ignore_whitespace(part),
+                ),
+            ];
+            for (suffix, check, ignore) in checks {
+                code += "#[test]\n";
+                if let Some(reason) = ignore {
+                    code += &format!("#[ignore = {reason:?}]\n");
+                }
+                code += &format!(
+                    "fn {}_part{}{suffix}() {{\n    ::aoc_common::examples::{check}::<Day>(::aoc_common::Part::{part:?}, {:?}, {expected:?}, {:?});\n}}\n\n",
+                    test_name(name),
+                    part.number(),
+                    example.input,
+                    example.params.to_string(),
+                );
+            }
+        }
+    }
+    code
+}
+
+fn read_examples(dir: &Path) -> Vec<(String, Example)> {
+    let mut paths = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|e| e == "toml"))
+            .collect::<Vec<_>>(),
+        // no examples yet, so no tests
+        Err(_) => Vec::new(),
+    };
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let text = fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("couldn't read {}: {e}", path.display()));
+            let example = Example::parse(&text)
+                .unwrap_or_else(|e| panic!("{} isn't a valid example: {e}", path.display()));
+            let stem = path.file_stem().unwrap().to_string_lossy().into_owned();
+            (stem, example)
+        })
+        .collect()
+}
+
+// for a day's build script - writes out a test for every example in the
+// crate's examples directory, and reruns whenever that changes
+pub fn generate_tests() {
+    let dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("examples");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let code = render_tests(&read_examples(&dir));
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(&out, code).unwrap_or_else(|e| panic!("couldn't write {}: {e}", out.display()));
+}
+
+// include the tests that the build script generated for this day's examples
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:ident) => {
+        #[cfg(test)]
+        mod examples {
+            type Day = super::$solution;
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, NoParams, ParseError};
+
+    struct Summer;
+
+    impl Solution for Summer {
+        const DAY: u8 = 99;
+
+        type Part1Input = Vec<u32>;
+        type Part2Input = Vec<u32>;
+        type Part1Output = u32;
+        type Part2Output = u32;
+        type Params = NoParams;
+
+        fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+            parse::lines(Self::DAY, input)
+                .map(|mut line| line.number())
+                .collect()
+        }
+
+        fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+            Self::parse_part1(input)
+        }
+
+        fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+            input.iter().sum()
+        }
+
+        fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+            input.iter().product()
+        }
+    }
+
+    #[test]
+    fn test_parse_example() {
+        let example = Example::parse(
+            r#"
+part1 = 6
+part2 = "six"
+ignore_part2 = "it's not a word"
+params = { row = 10 }
+input = '''
+1
+2
+3
+'''
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(example.input, "1\n2\n3\n");
+        assert_eq!(example.answer(Part::One), Some("6"));
+        assert_eq!(example.answer(Part::Two), Some("six"));
+        assert_eq!(example.ignore(Part::Two), Some("it's not a word"));
+        assert_eq!(example.params["row"].as_integer(), Some(10));
+
+        assert!(Example::parse("input = ''\npart3 = 1\n").is_err());
+        assert!(Example::parse("part1 = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_render_tests() {
+        let example = Example::parse("part2 = 6\nignore_part2 = \"slow\"\ninput = '1'\n").unwrap();
+        let code = render_tests(&[("Larger-Example".to_string(), example)]);
+
+        assert!(!code.contains("part1()"));
+        assert!(code.contains("#[ignore = \"slow\"]\nfn larger_example_part2()"));
+        assert!(code.contains("check::<Day>(::aoc_common::Part::Two, \"1\", \"6\", \"\")"));
+        assert!(code.contains("#[ignore = \"slow\"]\nfn larger_example_part2_whitespace()"));
+        assert!(
+            code.contains("check_whitespace::<Day>(::aoc_common::Part::Two, \"1\", \"6\", \"\")")
+        );
+
+        let example =
+            Example::parse("part1 = 6\nignore_whitespace = \"slow\"\ninput = '1'\n").unwrap();
+        let code = render_tests(&[("example".to_string(), example)]);
+        assert!(code.contains("#[test]\nfn example_part1()"));
+        assert!(code.contains("#[ignore = \"slow\"]\nfn example_part1_whitespace()"));
+    }
+
+    #[test]
+    fn test_check() {
+        check::<Summer>(Part::One, "1\n2\n3\n", "6", "");
+        check::<Summer>(Part::Two, "1\n2\n3\n", "6", "");
+    }
+
+    #[test]
+    fn test_whitespace_variants() {
+        let variants = whitespace_variants("1\n2\n");
+        let inputs = variants
+            .iter()
+            .map(|(_, input)| input.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(inputs, ["1\r\n2\r\n", "1  \n2  \n", "1\n2", "\n1\n2\n\n\n"]);
+
+        check_whitespace::<Summer>(Part::One, "1\n2\n3\n", "6", "");
+    }
+
+    #[test]
+    #[should_panic(expected = "with windows line endings")]
+    fn test_check_whitespace_wrong_answer() {
+        check_whitespace::<Summer>(Part::One, "1\n2\n", "4", "");
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong answer for part 2")]
+    fn test_check_wrong_answer() {
+        check::<Summer>(Part::Two, "1\n2\n4\n", "6", "");
+    }
+
+    #[test]
+    #[should_panic(expected = "the example's parameters don't fit")]
+    fn test_check_unknown_params() {
+        check::<Summer>(Part::One, "1\n", "1", "row = 10");
+    }
+}
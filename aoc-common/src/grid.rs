@@ -0,0 +1,257 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    parse::{self, Lines},
+    Coord, ParseError,
+};
+
+pub type Position = Coord<usize>;
+
+// a dense, rectangular grid, stored a row at a time.
+// x goes left-to-right and y goes top-to-bottom, both starting at 0 -
+// the same way round as the puzzle input
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid needs {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    // parse a whole input that's a map of characters
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        convert: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::from_lines(&mut parse::lines(day, input), expected, convert)
+    }
+
+    // parse the rest of these lines as a map of characters. every line has to be
+    // the same length, and the conversion returns None for characters that
+    // aren't allowed (which are then reported as not being what was expected)
+    pub fn from_lines(
+        lines: &mut Lines,
+        expected: &str,
+        convert: impl Fn(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for mut line in lines.by_ref() {
+            let mut row_width = 0;
+            while !line.is_empty() {
+                // a long row is wrong from the first character that doesn't fit
+                if width == Some(row_width) {
+                    return Err(line.error("end of line (rows have to be the same length)"));
+                }
+                cells.push(line.map_char(expected, &convert)?);
+                row_width += 1;
+            }
+
+            match width {
+                None if row_width == 0 => return Err(line.error(expected)),
+                None => width = Some(row_width),
+                Some(width) if row_width < width => {
+                    return Err(line.error(format!("{expected} (rows have to be the same length)")))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Self::new(width, height, cells)),
+            None => Err(lines.end_error(expected)),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[position.y * self.width + position.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.y * self.width + position.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    // every position in the grid, a row at a time
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Position { x, y }))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    // the first position (reading order) whose cell matches
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Position> {
+        self.iter().find(|(_, t)| predicate(t)).map(|(p, _)| p)
+    }
+
+    pub fn map<U>(&self, convert: impl Fn(&T) -> U) -> Grid<U> {
+        Grid::new(
+            self.width,
+            self.height,
+            self.cells.iter().map(convert).collect(),
+        )
+    }
+
+    // the positions up, down, left and right of here, that are actually in the grid
+    pub fn neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &[(0, -1), (0, 1), (-1, 0), (1, 0)])
+    }
+
+    // the positions diagonally next to here, that are actually in the grid
+    pub fn diagonal_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.offsets(position, &[(-1, -1), (1, -1), (-1, 1), (1, 1)])
+    }
+
+    // all eight positions around here, that are actually in the grid
+    pub fn all_neighbours(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        self.neighbours(position)
+            .chain(self.diagonal_neighbours(position))
+    }
+
+    fn offsets(
+        &self,
+        position: Position,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Position> + '_ {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let neighbour = Position {
+                x: position.x.checked_add_signed(dx)?,
+                y: position.y.checked_add_signed(dy)?,
+            };
+            self.contains(neighbour).then_some(neighbour)
+        })
+    }
+
+    // draw the grid a character per cell, a line per row
+    pub fn render(&self, convert: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&convert).collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{position:?} is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(8, input, "a digit", |c| c.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Position { x: 2, y: 1 }], 6);
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(
+            grid.render(|d| char::from_digit(*d, 10).unwrap()),
+            "123\n456\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = digits("123\n4x6\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "a digit");
+
+        let error = digits("123\n4567\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 4));
+
+        let error = digits("123\n45\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::filled(3, 3, ());
+        let corner = grid.neighbours(Position { x: 0, y: 0 }).collect::<Vec<_>>();
+        assert_eq!(
+            corner,
+            vec![Position { x: 0, y: 1 }, Position { x: 1, y: 0 }]
+        );
+
+        assert_eq!(grid.neighbours(Position { x: 1, y: 1 }).count(), 4);
+        assert_eq!(grid.diagonal_neighbours(Position { x: 2, y: 0 }).count(), 1);
+        assert_eq!(grid.all_neighbours(Position { x: 1, y: 1 }).count(), 8);
+        assert_eq!(grid.all_neighbours(Position { x: 2, y: 2 }).count(), 3);
+    }
+}
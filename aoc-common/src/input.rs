@@ -0,0 +1,181 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::parse::ParseError;
+
+// where the puzzle input came from - only really used for error messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    // a path of "-" means stdin, same as not giving one at all
+    pub fn from_path(path: Option<&Path>) -> Self {
+        match path {
+            Some(path) if path != Path::new("-") => Source::File(path.to_owned()),
+            _ => Source::Stdin,
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "'{}'", path.display()),
+            Source::Stdin => write!(f, "stdin"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    // no path given, and nothing piped in either
+    Missing,
+    Unreadable(Source, io::Error),
+    Empty(Source),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing => write!(
+                f,
+                "no puzzle input given - pass the path to an input file, or pipe one in on stdin"
+            ),
+            InputError::Unreadable(source, error) => {
+                write!(f, "couldn't read puzzle input from {source}: {error}")
+            }
+            InputError::Empty(source) => write!(f, "puzzle input from {source} is empty"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+// read the puzzle input from a file, or from stdin if there isn't one (or it's "-")
+pub fn load(path: Option<&Path>) -> Result<String, InputError> {
+    let source = Source::from_path(path);
+    let contents = match &source {
+        Source::File(path) => match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => return Err(InputError::Unreadable(source, e)),
+        },
+        Source::Stdin => {
+            let mut stdin = io::stdin();
+            // don't sit there waiting for someone to type their input in
+            if path.is_none() && stdin.is_terminal() {
+                return Err(InputError::Missing);
+            }
+            let mut contents = String::new();
+            if let Err(e) = stdin.read_to_string(&mut contents) {
+                return Err(InputError::Unreadable(Source::Stdin, e));
+            }
+            contents
+        }
+    };
+
+    if contents.trim().is_empty() {
+        return Err(InputError::Empty(source));
+    }
+
+    Ok(contents)
+}
+
+// the directory real inputs are kept in, laid out by `path_in`
+pub const DIR: &str = "inputs";
+
+// where a day's input goes in a directory of them, in a directory per year and
+// day like `inputs/2022/day01/input.txt`. the runner downloads them there, and
+// benchmarks and the answers file look for them there too
+pub fn path_in(dir: &Path, year: u16, day: u8) -> PathBuf {
+    dir.join(year.to_string())
+        .join(format!("day{day:02}"))
+        .join("input.txt")
+}
+
+fn path_from_args() -> Option<PathBuf> {
+    std::env::args_os().nth(1).map(PathBuf::from)
+}
+
+// the usual entry point for a day's binary: input path is the first argument
+pub fn from_args() -> Result<String, InputError> {
+    load(path_from_args().as_deref())
+}
+
+// same as above, but bail out with a readable message rather than a panic
+pub fn from_args_or_exit() -> String {
+    match from_args() {
+        Ok(input) => input,
+        Err(e) => {
+            let program = std::env::args().next().unwrap_or_else(|| "aoc".to_owned());
+            eprintln!("error: {e}");
+            eprintln!("usage: {program} [INPUT_FILE]");
+            process::exit(1);
+        }
+    }
+}
+
+// unwrap the result of an input generator, or point out what's wrong with the
+// input and bail out
+pub fn parse_or_exit<T>(parsed: Result<T, ParseError>) -> T {
+    match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                e.diagnostic(&Source::from_path(path_from_args().as_deref()))
+            );
+            process::exit(1);
+        }
+    }
+}
+
+#[test]
+fn test_load_from_file() {
+    let path = std::env::temp_dir().join(format!("aoc-common-input-{}.txt", process::id()));
+    fs::write(&path, "1000\n2000\n").unwrap();
+    let contents = load(Some(&path));
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(contents.unwrap(), "1000\n2000\n");
+}
+
+#[test]
+fn test_path_in() {
+    assert_eq!(
+        path_in(Path::new(DIR), 2022, 1),
+        Path::new("inputs/2022/day01/input.txt")
+    );
+    assert_eq!(
+        path_in(Path::new("x"), 2015, 25),
+        Path::new("x/2015/day25/input.txt")
+    );
+}
+
+#[test]
+fn test_load_missing_file() {
+    let path = Path::new("definitely/not/an/input.txt");
+    let error = load(Some(path)).unwrap_err();
+
+    assert!(matches!(error, InputError::Unreadable(Source::File(_), _)));
+    assert!(error
+        .to_string()
+        .starts_with("couldn't read puzzle input from 'definitely/not/an/input.txt'"));
+}
+
+#[test]
+fn test_load_empty_file() {
+    let path = std::env::temp_dir().join(format!("aoc-common-empty-{}.txt", process::id()));
+    fs::write(&path, "\n").unwrap();
+    let error = load(Some(&path)).unwrap_err();
+    fs::remove_file(&path).unwrap();
+
+    assert!(matches!(error, InputError::Empty(_)));
+}
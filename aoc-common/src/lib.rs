@@ -0,0 +1,22 @@
+//! Bits and pieces shared between the individual days.
+
+pub mod coord;
+pub mod cycle;
+pub mod differential;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod params;
+pub mod parse;
+pub mod rng;
+pub mod search;
+pub mod simulation;
+pub mod solution;
+pub mod stats;
+
+pub use coord::Coord;
+pub use grid::Grid;
+pub use params::{NoParams, Overrides};
+pub use parse::ParseError;
+pub use simulation::Simulation;
+pub use solution::{Part, RunError, Runnable, Solution};
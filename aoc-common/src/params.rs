@@ -0,0 +1,162 @@
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+// changes to some of a day's parameters, by name. whatever isn't mentioned
+// keeps the puzzle's value
+pub type Overrides = toml::Table;
+
+// the parameters for days that don't have anything worth changing
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NoParams {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParamsError {
+    Unknown { name: String, known: Vec<String> },
+    Invalid(String),
+}
+
+impl Display for ParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamsError::Unknown { name, known } if known.is_empty() => {
+                write!(
+                    f,
+                    "there's no parameter `{name}` - this day doesn't have any"
+                )
+            }
+            ParamsError::Unknown { name, known } => write!(
+                f,
+                "there's no parameter `{name}` - it has to be one of {}",
+                known
+                    .iter()
+                    .map(|k| format!("`{k}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParamsError::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParamsError {}
+
+// the defaults as a table, so overrides can be laid over the top
+pub fn defaults<P: Default + Serialize>() -> Overrides {
+    Overrides::try_from(P::default()).expect("parameters have to be a struct")
+}
+
+// the puzzle's parameters, with any of them changed that are mentioned in the overrides
+pub fn with_overrides<P>(overrides: &Overrides) -> Result<P, ParamsError>
+where
+    P: Default + Serialize + DeserializeOwned,
+{
+    if overrides.is_empty() {
+        return Ok(P::default());
+    }
+
+    let mut params = defaults::<P>();
+    for (name, value) in overrides {
+        if !params.contains_key(name) {
+            return Err(ParamsError::Unknown {
+                name: name.clone(),
+                known: params.keys().cloned().collect(),
+            });
+        }
+        params.insert(name.clone(), value.clone());
+    }
+
+    params
+        .try_into()
+        .map_err(|e: toml::de::Error| ParamsError::Invalid(e.message().to_string()))
+}
+
+// turn `name=value` into an override. the value is read as TOML if it can be
+// (so numbers are numbers), and just taken as a string otherwise
+pub fn parse_override(text: &str) -> Result<(String, toml::Value), String> {
+    let (name, value) = text
+        .split_once('=')
+        .ok_or_else(|| format!("`{text}` should look like `name=value`"))?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(format!("`{text}` doesn't say which parameter to change"));
+    }
+
+    let value = value.trim();
+    let value = format!("value = {value}")
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+    Ok((name.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Sample {
+        row: i64,
+        rounds: usize,
+    }
+
+    impl Default for Sample {
+        fn default() -> Self {
+            Self {
+                row: 2_000_000,
+                rounds: 10,
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_overrides() {
+        assert_eq!(with_overrides(&Overrides::new()), Ok(Sample::default()));
+
+        let overrides = Overrides::from_iter([("row".to_string(), toml::Value::Integer(10))]);
+        assert_eq!(
+            with_overrides(&overrides),
+            Ok(Sample {
+                row: 10,
+                rounds: 10
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_overrides() {
+        let unknown = Overrides::from_iter([("rows".to_string(), toml::Value::Integer(10))]);
+        let error = with_overrides::<Sample>(&unknown).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "there's no parameter `rows` - it has to be one of `rounds`, `row`"
+        );
+
+        let error = with_overrides::<NoParams>(&unknown).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "there's no parameter `rows` - this day doesn't have any"
+        );
+
+        let wrong_type = Overrides::from_iter([("rounds".to_string(), toml::Value::from(-1))]);
+        assert!(matches!(
+            with_overrides::<Sample>(&wrong_type),
+            Err(ParamsError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(
+            parse_override("row=10"),
+            Ok(("row".to_string(), toml::Value::Integer(10)))
+        );
+        assert_eq!(
+            parse_override(" start = AA "),
+            Ok(("start".to_string(), toml::Value::from("AA")))
+        );
+        assert!(parse_override("row").is_err());
+        assert!(parse_override("=10").is_err());
+    }
+}
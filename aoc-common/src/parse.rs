@@ -0,0 +1,449 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::input::Source;
+
+// something in the puzzle input that we couldn't make sense of
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    // both 1-based, like an editor would show them
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    // the whole offending line, so we can point at the problem
+    pub source_line: String,
+}
+
+impl ParseError {
+    // render the error the way rustc would, pointing at where it went wrong
+    pub fn diagnostic(&self, source: &Source) -> String {
+        let location = match source {
+            Source::File(path) => format!("{}:{}:{}", path.display(), self.line, self.column),
+            Source::Stdin => format!("<stdin>:{}:{}", self.line, self.column),
+        };
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        // tabs would throw the marker out of line - show them as a single space
+        let shown_line = self.source_line.replace('\t', " ");
+        let marker = " ".repeat(self.column - 1) + "^";
+
+        format!(
+            "error: expected {}, found {}\n{gutter}--> {location}\n{gutter} |\n{line_number} | {shown_line}\n{gutter} | {marker}\n{gutter} |\n{gutter} = note: while parsing the input for day {}",
+            self.expected, self.found, self.day
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// a single line of input, with a cursor that moves along it as we parse
+#[derive(Clone, Debug)]
+pub struct Line<'a> {
+    day: u8,
+    number: usize,
+    text: &'a str,
+    // byte offset of the cursor into the text
+    position: usize,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(day: u8, number: usize, text: &'a str) -> Self {
+        Self {
+            day,
+            number,
+            text,
+            position: 0,
+        }
+    }
+
+    pub fn line_number(&self) -> usize {
+        self.number
+    }
+
+    // the whole line, regardless of how much we've parsed
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    // the bit of the line we haven't parsed yet
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    // an error at the current cursor position
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.position, expected)
+    }
+
+    // an error at some earlier (byte) position in this line
+    pub fn error_at(&self, position: usize, expected: impl Into<String>) -> ParseError {
+        let remaining = &self.text[position..];
+        let token = remaining
+            .split(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or("");
+        let found = match remaining.chars().next() {
+            None => "end of line".to_owned(),
+            Some(c) if c.is_whitespace() => format!("{c:?}"),
+            // don't quote the rest of a really long line back at them
+            _ if token.chars().count() > 20 => {
+                format!("`{}...`", token.chars().take(20).collect::<String>())
+            }
+            _ => format!("`{token}`"),
+        };
+        ParseError {
+            day: self.day,
+            line: self.number,
+            column: self.text[..position].chars().count() + 1,
+            expected: expected.into(),
+            found,
+            source_line: self.text.to_owned(),
+        }
+    }
+
+    // consume this exact text, or complain that it isn't there
+    pub fn tag(&mut self, tag: &str) -> Result<(), ParseError> {
+        if self.try_tag(tag) {
+            Ok(())
+        } else {
+            let expected = match tag.trim() {
+                "" => "a space".to_owned(),
+                trimmed => format!("`{trimmed}`"),
+            };
+            // if the leading space is there, point at the word after it instead
+            let leading = tag.len() - tag.trim_start().len();
+            let position = match self.rest().starts_with(&tag[..leading]) {
+                true => self.position + leading,
+                false => self.position,
+            };
+            Err(self.error_at(position, expected))
+        }
+    }
+
+    // consume this exact text if it's there - returns whether it was
+    pub fn try_tag(&mut self, tag: &str) -> bool {
+        if self.rest().starts_with(tag) {
+            self.position += tag.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c: char| !predicate(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    // like take_while, but there has to be at least one matching character
+    pub fn take_while1(
+        &mut self,
+        expected: &str,
+        predicate: impl Fn(char) -> bool,
+    ) -> Result<&'a str, ParseError> {
+        let taken = self.take_while(predicate);
+        if taken.is_empty() {
+            Err(self.error(expected))
+        } else {
+            Ok(taken)
+        }
+    }
+
+    // a (possibly negative) whole number
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.position;
+        let sign = if self.rest().starts_with('-') { 1 } else { 0 };
+        let digits = self.rest()[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest().len() - sign);
+        if digits == 0 {
+            return Err(self.error("a number"));
+        }
+
+        let text = &self.rest()[..sign + digits];
+        match text.parse() {
+            Ok(number) => {
+                self.position += text.len();
+                Ok(number)
+            }
+            Err(_) => Err(self.error_at(
+                start,
+                format!("a number that fits in a {}", std::any::type_name::<T>()),
+            )),
+        }
+    }
+
+    // any single character
+    pub fn char(&mut self, expected: &str) -> Result<char, ParseError> {
+        match self.rest().chars().next() {
+            Some(c) => {
+                self.position += c.len_utf8();
+                Ok(c)
+            }
+            None => Err(self.error(expected)),
+        }
+    }
+
+    // a single character, converted into something else - the conversion returns None
+    // for characters that aren't allowed here
+    pub fn map_char<T>(
+        &mut self,
+        expected: &str,
+        convert: impl Fn(char) -> Option<T>,
+    ) -> Result<T, ParseError> {
+        let start = self.position;
+        let c = self.char(expected)?;
+        convert(c).ok_or_else(|| self.error_at(start, expected))
+    }
+
+    // whichever of these tags comes next, mapped to its value
+    pub fn one_of<S: AsRef<str>, T: Clone>(&mut self, options: &[(S, T)]) -> Result<T, ParseError> {
+        for (tag, value) in options {
+            if self.try_tag(tag.as_ref()) {
+                return Ok(value.clone());
+            }
+        }
+
+        let expected = options
+            .iter()
+            .map(|(tag, _)| format!("`{}`", tag.as_ref()))
+            .collect::<Vec<_>>();
+        let expected = match expected.split_last() {
+            Some((last, rest)) if !rest.is_empty() => format!("{} or {last}", rest.join(", ")),
+            _ => expected.join(""),
+        };
+        Err(self.error(expected))
+    }
+
+    // there shouldn't be anything left on the line
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+// the lines of (part of) the input, which knows where it ends so that
+// running out of lines early can be reported properly
+pub struct Lines<'a> {
+    day: u8,
+    lines: std::vec::IntoIter<Line<'a>>,
+    // the line number just past the last one, and what's there instead
+    end_line: usize,
+    end_description: &'static str,
+}
+
+impl<'a> Lines<'a> {
+    // the next line, which has to be there
+    pub fn expect(&mut self, expected: &str) -> Result<Line<'a>, ParseError> {
+        self.lines.next().ok_or_else(|| self.end_error(expected))
+    }
+
+    // there shouldn't be any more lines
+    pub fn end(&mut self) -> Result<(), ParseError> {
+        match self.lines.next() {
+            None => Ok(()),
+            Some(line) => Err(line.error(self.end_description)),
+        }
+    }
+
+    // an error just after the last line
+    pub fn end_error(&self, expected: &str) -> ParseError {
+        ParseError {
+            day: self.day,
+            line: self.end_line,
+            column: 1,
+            expected: expected.to_owned(),
+            found: self.end_description.to_owned(),
+            source_line: String::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lines.next()
+    }
+}
+
+// every line of the input, numbered from 1. how the file was saved shouldn't
+// matter, so trailing whitespace (which takes in the `\r` of windows line
+// endings) is dropped from each line, as are blank lines at the start and end.
+// leading spaces are kept, since day 22's map needs them
+pub fn lines(day: u8, input: &str) -> Lines<'_> {
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .map(|(index, text)| Line::new(day, index + 1, text.trim_end()))
+        .skip_while(|line| line.text().is_empty())
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.text().is_empty()) {
+        lines.pop();
+    }
+    Lines {
+        day,
+        end_line: lines.last().map_or(1, |line| line.line_number() + 1),
+        lines: lines.into_iter(),
+        end_description: "end of input",
+    }
+}
+
+// groups of lines separated by blank lines (and with the blank lines taken out)
+pub fn blocks(day: u8, input: &str) -> Vec<Lines<'_>> {
+    let mut blocks = Vec::new();
+    let mut current_block = Vec::new();
+    for line in lines(day, input) {
+        if line.text().is_empty() {
+            if !current_block.is_empty() {
+                blocks.push(Lines {
+                    day,
+                    end_line: line.line_number(),
+                    lines: std::mem::take(&mut current_block).into_iter(),
+                    end_description: "a blank line",
+                });
+            }
+        } else {
+            current_block.push(line);
+        }
+    }
+
+    if let Some(last_line) = current_block.last() {
+        blocks.push(Lines {
+            day,
+            end_line: last_line.line_number() + 1,
+            lines: current_block.into_iter(),
+            end_description: "end of input",
+        });
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsing_a_line() {
+        let mut line = Line::new(15, 1, "Sensor at x=2, y=-18");
+        line.tag("Sensor at x=").unwrap();
+        assert_eq!(line.number::<i32>().unwrap(), 2);
+        line.tag(", y=").unwrap();
+        assert_eq!(line.number::<i32>().unwrap(), -18);
+        assert!(line.end().is_ok());
+    }
+
+    #[test]
+    fn test_error_location() {
+        let mut line = Line::new(2, 3, "A Q");
+        line.tag("A ").unwrap();
+        let error = line.one_of(&[("X", 1), ("Y", 2), ("Z", 3)]).unwrap_err();
+
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.expected, "`X`, `Y` or `Z`");
+        assert_eq!(error.found, "`Q`");
+        assert_eq!(
+            error.to_string(),
+            "day 2, line 3, column 3: expected `X`, `Y` or `Z`, found `Q`"
+        );
+    }
+
+    #[test]
+    fn test_number_errors() {
+        let mut line = Line::new(1, 1, "abc");
+        assert_eq!(line.number::<u32>().unwrap_err().expected, "a number");
+
+        let mut line = Line::new(1, 1, "300");
+        assert_eq!(
+            line.number::<u8>().unwrap_err().expected,
+            "a number that fits in a u8"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let mut line = Line::new(9, 12, "R 4x");
+        line.tag("R ").unwrap();
+        line.number::<i32>().unwrap();
+        let error = line.end().unwrap_err();
+        let expected = r#"error: expected end of line, found `x`
+  --> input.txt:12:4
+   |
+12 | R 4x
+   |    ^
+   |
+   = note: while parsing the input for day 9"#;
+
+        assert_eq!(
+            error.diagnostic(&Source::File("input.txt".into())),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_lines_ignore_how_the_file_was_saved() {
+        let texts = |input| {
+            lines(1, input)
+                .map(|line| (line.line_number(), line.text()))
+                .collect::<Vec<_>>()
+        };
+        let expected = [(2, "  a"), (3, ""), (4, "b")];
+        assert_eq!(texts("\n  a\n\nb\n"), expected);
+        assert_eq!(texts("\r\n  a\r\n\r\nb\r\n"), expected);
+        assert_eq!(texts("\n  a \n\t\nb"), expected);
+        assert_eq!(texts("\n  a\n\nb\n\n  \n"), expected);
+
+        // running out is reported straight after the last line with anything on it
+        let mut trailing_blanks = lines(1, "a\n\n\n");
+        trailing_blanks.expect("a").unwrap();
+        assert_eq!(trailing_blanks.expect("b").unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\nc\n";
+        let mut blocks = blocks(11, input);
+        assert_eq!(blocks.len(), 2);
+
+        let first = &mut blocks[0];
+        assert_eq!(first.expect("a").unwrap().text(), "a");
+        assert_eq!(first.expect("b").unwrap().text(), "b");
+        let error = first.expect("c").unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (3, "a blank line"));
+
+        let second = &mut blocks[1];
+        assert_eq!(second.expect("c").unwrap().line_number(), 4);
+        let error = second.expect("d").unwrap_err();
+        assert_eq!((error.line, error.found.as_str()), (5, "end of input"));
+    }
+}
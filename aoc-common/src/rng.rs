@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+// a small, seedable random number generator (splitmix64) - nothing here needs
+// anything better, and the same seed always gives the same numbers, so any input
+// made from one can be made again
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // anything from 0 up to (but not including) `n`
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "nothing to pick from");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "empty range {low}..={high}");
+        let width = high.abs_diff(low).wrapping_add(1);
+        if width == 0 {
+            // the whole of i64
+            return self.next_u64() as i64;
+        }
+        low.wrapping_add((self.next_u64() % width) as i64)
+    }
+
+    // true one time in `n`, on average
+    pub fn one_in(&mut self, n: usize) -> bool {
+        self.below(n) == 0
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+    }
+
+    #[test]
+    fn test_range() {
+        let mut rng = Rng::new(7);
+        let rolls = (0..1000).map(|_| rng.range(-2..=3)).collect::<Vec<_>>();
+        assert!(rolls.iter().all(|r| (-2..=3).contains(r)));
+        // every value turns up
+        for value in -2..=3 {
+            assert!(rolls.contains(&value), "never rolled {value}");
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(i64::MIN..=i64::MAX);
+    }
+}
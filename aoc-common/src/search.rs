@@ -0,0 +1,237 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+// what we found out about a graph by searching it from a start node.
+// nodes that couldn't be reached aren't in either map
+#[derive(Clone, Debug)]
+pub struct SearchResult<N, C> {
+    pub start: N,
+    // shortest distance from the start to each node
+    pub distances: HashMap<N, C>,
+    // the node before each one on a shortest path back to the start
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> SearchResult<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), zero)]),
+            predecessors: HashMap::new(),
+            start,
+        }
+    }
+
+    pub fn distance_to(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    // a shortest path from the start to here, including both ends
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut current = node;
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// breadth-first search, for when every step costs the same
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut result = SearchResult::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let distance = result.distances[&node] + 1;
+        for next in successors(&node) {
+            if !result.distances.contains_key(&next) {
+                result.distances.insert(next.clone(), distance);
+                result.predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+// something on the heap - the cheapest estimate comes out first
+struct Candidate<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Candidate<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Candidate<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Candidate<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Candidate<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap is a max-heap, so flip it round
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+// the heap-based search behind both dijkstra and A*. stops as soon as it
+// settles a goal node, which it hands back along with everything it found
+fn best_first<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Option<N>, SearchResult<N, C>)
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut result = SearchResult::new(start.clone(), C::default());
+    let mut heap = BinaryHeap::from([Candidate {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Candidate { cost, node, .. }) = heap.pop() {
+        // we already found a cheaper way here, and dealt with it then
+        if cost > result.distances[&node] {
+            continue;
+        }
+        if is_goal(&node) {
+            return (Some(node), result);
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            let improved = match result.distances.get(&next) {
+                Some(&existing) => next_cost < existing,
+                None => true,
+            };
+            if improved {
+                result.distances.insert(next.clone(), next_cost);
+                result.predecessors.insert(next.clone(), node.clone());
+                heap.push(Candidate {
+                    estimate: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    (None, result)
+}
+
+// shortest distances from the start to everywhere reachable, when steps have
+// different (non-negative) costs
+pub fn dijkstra<N, C, I>(start: N, successors: impl FnMut(&N) -> I) -> SearchResult<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, successors, |_| C::default(), |_| false).1
+}
+
+// shortest route from the start to a goal, guided by a heuristic that mustn't
+// overestimate the remaining cost. returns the goal it reached (if any) - the
+// search stops there, so the maps only cover the nodes it needed to look at
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(N, SearchResult<N, C>)>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    match best_first(start, successors, heuristic, is_goal) {
+        (Some(goal), result) => Some((goal, result)),
+        (None, _) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a small weighted graph:
+    //   a --1-- b --1-- c
+    //    \             /
+    //     ------5------
+    fn edges(node: &char) -> Vec<(char, u32)> {
+        match node {
+            'a' => vec![('b', 1), ('c', 5)],
+            'b' => vec![('a', 1), ('c', 1)],
+            'c' => vec![('a', 5), ('b', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let result = bfs('a', |n| edges(n).into_iter().map(|(n, _)| n));
+        assert_eq!(result.distance_to(&'b'), Some(1));
+        assert_eq!(result.distance_to(&'c'), Some(1));
+        assert_eq!(result.distance_to(&'z'), None);
+        assert_eq!(result.path_to(&'c'), Some(vec!['a', 'c']));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra('a', edges);
+        assert_eq!(result.distance_to(&'a'), Some(0));
+        assert_eq!(result.distance_to(&'c'), Some(2));
+        assert_eq!(result.path_to(&'c'), Some(vec!['a', 'b', 'c']));
+    }
+
+    #[test]
+    fn test_astar_on_a_line() {
+        // walking along the number line, with the distance left as the heuristic
+        let target = 10i32;
+        let (goal, result) = astar(
+            0i32,
+            |&n| [(n - 1, 1), (n + 1, 1)],
+            |&n| (target - n).abs(),
+            |&n| n == target,
+        )
+        .unwrap();
+
+        assert_eq!(goal, 10);
+        assert_eq!(result.distance_to(&goal), Some(10));
+        assert_eq!(result.path_to(&goal).unwrap().len(), 11);
+        // it shouldn't have wandered off in the wrong direction much
+        assert!(result.distances.keys().all(|&n| n >= -1));
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        assert!(astar('a', edges, |_| 0, |&n| n == 'z').is_none());
+    }
+}
@@ -0,0 +1,104 @@
+// a puzzle that plays out one tick at a time - a crane move, a CPU cycle, a
+// grain of sand, a round of elves moving. driving them all through this means
+// any of them can be paused partway through and looked at
+pub trait Simulation {
+    // move on by one tick. returns false once the simulation's finished:
+    // either there was nothing left to do, or nothing changed
+    fn step(&mut self) -> bool;
+
+    // how many ticks there have been since the start
+    fn steps(&self) -> usize;
+
+    // the current state, drawn out as text
+    fn render(&self) -> String;
+
+    // keep stepping until `done` says to stop, or the simulation finishes.
+    // `done` is checked before each step, so if it already holds nothing moves.
+    // returns whether `done` was met
+    fn run_until(&mut self, mut done: impl FnMut(&Self) -> bool) -> bool
+    where
+        Self: Sized,
+    {
+        loop {
+            if done(self) {
+                return true;
+            }
+            if !self.step() {
+                return false;
+            }
+        }
+    }
+
+    // take up to this many more steps. returns false if it finished first
+    fn run_for(&mut self, steps: usize) -> bool
+    where
+        Self: Sized,
+    {
+        let target = self.steps() + steps;
+        self.run_until(|s| s.steps() >= target)
+    }
+
+    // step until it finishes - only for simulations that do
+    fn run(&mut self)
+    where
+        Self: Sized,
+    {
+        self.run_until(|_| false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counts up to a limit, and then stops
+    struct Counter {
+        count: usize,
+        limit: usize,
+    }
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.count == self.limit {
+                return false;
+            }
+            self.count += 1;
+            true
+        }
+
+        fn steps(&self) -> usize {
+            self.count
+        }
+
+        fn render(&self) -> String {
+            format!("{}/{}", self.count, self.limit)
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut counter = Counter { count: 0, limit: 5 };
+        assert!(counter.run_until(|c| c.count == 3));
+        assert_eq!(counter.render(), "3/5");
+        // already there, so nothing happens
+        assert!(counter.run_until(|c| c.count == 3));
+        assert_eq!(counter.steps(), 3);
+
+        assert!(!counter.run_until(|c| c.count == 10));
+        assert_eq!(counter.steps(), 5);
+    }
+
+    #[test]
+    fn test_run_for() {
+        let mut counter = Counter { count: 0, limit: 5 };
+        assert!(counter.run_for(2));
+        assert!(counter.run_for(2));
+        assert_eq!(counter.steps(), 4);
+        assert!(!counter.run_for(2));
+        assert_eq!(counter.steps(), 5);
+
+        let mut counter = Counter { count: 0, limit: 5 };
+        counter.run();
+        assert_eq!(counter.render(), "5/5");
+    }
+}
@@ -0,0 +1,313 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    params::{self, Overrides, ParamsError},
+    parse::ParseError,
+    rng::Rng,
+    stats,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn both() -> [Part; 2] {
+        [Part::One, Part::Two]
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = u8;
+
+    fn try_from(number: u8) -> Result<Self, Self::Error> {
+        match number {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(n),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// day 25 only has the one puzzle - use this as its part 2 answer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(no puzzle)")
+    }
+}
+
+// ties together a day's input generators and solvers.
+// most days parse the input the same way for both parts, so their
+// part 2 input type is just the same as the part 1 one.
+// the params are the numbers the puzzle text gives (how many rounds, which
+// row...) - their defaults are the real puzzle's, and days without any use NoParams
+pub trait Solution {
+    const DAY: u8;
+    const HAS_PART2: bool = true;
+
+    type Part1Input;
+    type Part2Input;
+    type Part1Output: Display;
+    type Part2Output: Display;
+    type Params: Default + Serialize + DeserializeOwned;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError>;
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError>;
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output;
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output;
+
+    // a made-up (but valid) puzzle input, to see how the solvers cope with bigger
+    // ones than the real puzzle. what `size` counts is up to the day - elves,
+    // sensors, valves...
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub answer: String,
+    // the solver's output type, like `usize` or `String`
+    pub answer_type: &'static str,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    // whatever the solver counted while it was working - always empty without
+    // the `stats` feature
+    pub stats: stats::Counts,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RunError {
+    Params(ParamsError),
+    Parse(ParseError),
+}
+
+impl Display for RunError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunError::Params(e) => write!(f, "{e}"),
+            RunError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for RunError {}
+
+// object-safe version of the above, so we can keep all the days in one list
+// and pick one at runtime
+pub trait Runnable {
+    fn day(&self) -> u8;
+    fn has_part(&self, part: Part) -> bool;
+    // the parameters that would be used, with these overrides
+    fn params(&self, overrides: &Overrides) -> Result<Overrides, ParamsError>;
+    // None if the day doesn't have this part
+    fn run(
+        &self,
+        part: Part,
+        input: &str,
+        overrides: &Overrides,
+    ) -> Option<Result<PartResult, RunError>>;
+    // None if the day can't make up inputs. the same seed always gives the same input
+    fn generate(&self, seed: u64, size: usize) -> Option<String>;
+}
+
+// just the type's own name, without the path to it
+fn type_label<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+impl<S: Solution> Runnable for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn has_part(&self, part: Part) -> bool {
+        part == Part::One || S::HAS_PART2
+    }
+
+    fn params(&self, overrides: &Overrides) -> Result<Overrides, ParamsError> {
+        let params = params::with_overrides::<S::Params>(overrides)?;
+        Ok(Overrides::try_from(params).expect("parameters have to be a struct"))
+    }
+
+    fn run(
+        &self,
+        part: Part,
+        input: &str,
+        overrides: &Overrides,
+    ) -> Option<Result<PartResult, RunError>> {
+        if !self.has_part(part) {
+            return None;
+        }
+
+        let params = match params::with_overrides::<S::Params>(overrides) {
+            Ok(params) => params,
+            Err(e) => return Some(Err(RunError::Params(e))),
+        };
+
+        // anything left over from before isn't this run's
+        stats::take();
+        let (answer, answer_type, parse_time, solve_time) = match part {
+            Part::One => {
+                let (parsed, parse_time) = timed(|| S::parse_part1(input));
+                let parsed = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(e) => return Some(Err(RunError::Parse(e))),
+                };
+                let (answer, solve_time) = timed(|| S::solve_part1(&parsed, &params));
+                let answer_type = type_label::<S::Part1Output>();
+                (answer.to_string(), answer_type, parse_time, solve_time)
+            }
+            Part::Two => {
+                let (parsed, parse_time) = timed(|| S::parse_part2(input));
+                let parsed = match parsed {
+                    Ok(parsed) => parsed,
+                    Err(e) => return Some(Err(RunError::Parse(e))),
+                };
+                let (answer, solve_time) = timed(|| S::solve_part2(&parsed, &params));
+                let answer_type = type_label::<S::Part2Output>();
+                (answer.to_string(), answer_type, parse_time, solve_time)
+            }
+        };
+
+        Some(Ok(PartResult {
+            answer,
+            answer_type,
+            parse_time,
+            solve_time,
+            stats: stats::take(),
+        }))
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        S::generate(&mut Rng::new(seed), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+    use crate::parse;
+
+    struct Multiplier;
+
+    #[derive(Serialize, Deserialize)]
+    struct Factor {
+        factor: u32,
+    }
+
+    impl Default for Factor {
+        fn default() -> Self {
+            Self { factor: 2 }
+        }
+    }
+
+    impl Solution for Multiplier {
+        const DAY: u8 = 99;
+        const HAS_PART2: bool = false;
+
+        type Part1Input = Vec<u32>;
+        type Part2Input = ();
+        type Part1Output = u32;
+        type Part2Output = NoAnswer;
+        type Params = Factor;
+
+        fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+            parse::lines(Self::DAY, input)
+                .map(|mut line| {
+                    let number = line.number()?;
+                    line.end()?;
+                    Ok(number)
+                })
+                .collect()
+        }
+
+        fn parse_part2(_input: &str) -> Result<Self::Part2Input, ParseError> {
+            Ok(())
+        }
+
+        fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+            input.iter().map(|i| i * params.factor).sum()
+        }
+
+        fn solve_part2(_input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+            NoAnswer
+        }
+    }
+
+    #[test]
+    fn test_runnable() {
+        let day: &dyn Runnable = &Multiplier;
+        let defaults = Overrides::new();
+        assert_eq!(day.day(), 99);
+        let result = day.run(Part::One, "1\n2\n3\n", &defaults).unwrap().unwrap();
+        assert_eq!((result.answer.as_str(), result.answer_type), ("12", "u32"));
+        assert!(!day.has_part(Part::Two));
+        assert_eq!(day.run(Part::Two, "1\n2\n3\n", &defaults), None);
+        assert_eq!(day.generate(1, 10), None);
+    }
+
+    #[test]
+    fn test_runnable_params() {
+        let triple = Overrides::from_iter([("factor".to_string(), toml::Value::Integer(3))]);
+        assert_eq!(
+            Multiplier
+                .run(Part::One, "1\n2\n3\n", &triple)
+                .unwrap()
+                .unwrap()
+                .answer,
+            "18"
+        );
+        assert_eq!(Multiplier.params(&triple), Ok(triple));
+
+        let unknown = Overrides::from_iter([("divisor".to_string(), toml::Value::Integer(3))]);
+        assert!(matches!(
+            Multiplier.run(Part::One, "1\n", &unknown),
+            Some(Err(RunError::Params(_)))
+        ));
+    }
+
+    #[test]
+    fn test_runnable_parse_error() {
+        let error = Multiplier
+            .run(Part::One, "1\nx\n3\n", &Overrides::new())
+            .unwrap()
+            .unwrap_err();
+        let RunError::Parse(error) = error else {
+            panic!("expected a parse error, got {error:?}");
+        };
+        assert_eq!((error.day, error.line, error.column), (99, 2, 1));
+    }
+}
@@ -0,0 +1,58 @@
+// counters for seeing how hard a search works: how many states it looks at, how
+// often the memo saves it the trouble, and which of its pruning rules fire.
+// they're only kept with the `stats` feature on - otherwise counting does nothing
+// at all, so the searches can call it as much as they like
+
+#[cfg(feature = "stats")]
+use std::{cell::RefCell, collections::BTreeMap};
+
+// what's been counted, by name
+pub type Counts = Vec<(&'static str, u64)>;
+
+pub const ENABLED: bool = cfg!(feature = "stats");
+
+#[cfg(feature = "stats")]
+thread_local! {
+    static COUNTS: RefCell<BTreeMap<&'static str, u64>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+// add one to this counter
+#[inline]
+pub fn count(name: &'static str) {
+    add(name, 1);
+}
+
+#[inline]
+pub fn add(name: &'static str, amount: u64) {
+    #[cfg(feature = "stats")]
+    COUNTS.with(|counts| *counts.borrow_mut().entry(name).or_default() += amount);
+    #[cfg(not(feature = "stats"))]
+    let _ = (name, amount);
+}
+
+// everything counted on this thread since last time, in order of name - so
+// related counters like `pruned: ...` end up together
+pub fn take() -> Counts {
+    #[cfg(feature = "stats")]
+    return COUNTS.with(|counts| counts.take().into_iter().collect());
+    #[cfg(not(feature = "stats"))]
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counting() {
+        take();
+        count("nodes expanded");
+        add("memo hits", 3);
+        count("nodes expanded");
+
+        if ENABLED {
+            assert_eq!(take(), [("memo hits", 3), ("nodes expanded", 2)]);
+        }
+        assert_eq!(take(), []);
+    }
+}
@@ -0,0 +1,321 @@
+use std::{fmt::Display, time::Duration};
+
+use aoc_common::{Overrides, Part, RunError, Runnable};
+use serde::{Deserialize, Serialize};
+
+// summary of a set of timings, all in nanoseconds
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: u64,
+    pub median: u64,
+    pub mean: u64,
+    pub std_dev: u64,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "need at least one sample");
+        let mut nanos = samples
+            .iter()
+            .map(|d| d.as_nanos() as u64)
+            .collect::<Vec<_>>();
+        nanos.sort_unstable();
+
+        let count = nanos.len();
+        let median = if count % 2 == 0 {
+            (nanos[count / 2 - 1] + nanos[count / 2]) / 2
+        } else {
+            nanos[count / 2]
+        };
+        let mean = nanos.iter().map(|&n| n as f64).sum::<f64>() / count as f64;
+        let variance = nanos
+            .iter()
+            .map(|&n| (n as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Self {
+            samples: count,
+            min: nanos[0],
+            median,
+            mean: mean.round() as u64,
+            std_dev: variance.sqrt().round() as u64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: u8,
+    pub parse: Stats,
+    pub solve: Stats,
+}
+
+// what the benchmarks were run on. timings for different inputs can't be
+// compared, so this goes in the report too
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InputSource {
+    // the puzzle inputs in `inputs/`
+    Real,
+    // inputs made up by each day's `generate`
+    Synthetic { size: usize, seed: u64 },
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Real => write!(f, "the real inputs"),
+            InputSource::Synthetic { size, seed } => {
+                write!(f, "made-up inputs of size {size} (seed {seed})")
+            }
+        }
+    }
+}
+
+// everything from one run of the benchmarks - this is what gets written out,
+// so that it can be compared against a later run
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub source: InputSource,
+    pub warm_up: usize,
+    pub samples: usize,
+    pub results: Vec<Measurement>,
+}
+
+// time parsing and solving one part, separately. the warm-up runs aren't counted -
+// they're just there to get the caches (and the CPU's clock speed) settled.
+// it's always the real puzzle's parameters, so runs can be compared
+pub fn measure(
+    solution: &dyn Runnable,
+    part: Part,
+    input: &str,
+    warm_up: usize,
+    samples: usize,
+) -> Result<Option<Measurement>, RunError> {
+    let mut parse_times = Vec::with_capacity(samples);
+    let mut solve_times = Vec::with_capacity(samples);
+    let defaults = Overrides::new();
+    for run in 0..warm_up + samples {
+        let Some(result) = solution.run(part, input, &defaults) else {
+            return Ok(None);
+        };
+        let result = result?;
+        if run >= warm_up {
+            parse_times.push(result.parse_time);
+            solve_times.push(result.solve_time);
+        }
+    }
+
+    Ok(Some(Measurement {
+        day: solution.day(),
+        part: part.number(),
+        parse: Stats::from_samples(&parse_times),
+        solve: Stats::from_samples(&solve_times),
+    }))
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub part: u8,
+    pub step: &'static str,
+    pub before: u64,
+    pub after: u64,
+    // relative change in the median, so 0.5 means it got 50% slower
+    pub change: f64,
+    pub regressed: bool,
+}
+
+// anything quicker than this is mostly noise, so don't call it a regression
+// however much it changed by
+const NOISE_FLOOR: u64 = 50_000;
+
+// compare the medians of two runs, for every day and part that's in both.
+// a step has regressed if it got slower by more than the threshold (0.1 being 10%).
+// both runs have to have timed the same inputs, or the numbers mean nothing
+pub fn compare(before: &Report, after: &Report, threshold: f64) -> Result<Vec<Change>, String> {
+    if before.source != after.source {
+        return Err(format!(
+            "the runs timed different inputs - {} before, and {} after",
+            before.source, after.source
+        ));
+    }
+
+    let mut changes = Vec::new();
+    for new in &after.results {
+        let Some(old) = before
+            .results
+            .iter()
+            .find(|m| (m.day, m.part) == (new.day, new.part))
+        else {
+            continue;
+        };
+
+        for (step, old_stats, new_stats) in [
+            ("parse", &old.parse, &new.parse),
+            ("solve", &old.solve, &new.solve),
+        ] {
+            let (before, after) = (old_stats.median, new_stats.median);
+            let change = (after as f64 - before as f64) / before.max(1) as f64;
+            changes.push(Change {
+                day: new.day,
+                part: new.part,
+                step,
+                before,
+                after,
+                change,
+                regressed: change > threshold && after.abs_diff(before) > NOISE_FLOOR,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+pub fn print_report(report: &Report) {
+    println!(
+        "{} samples each, after {} warm-up runs, on {}",
+        report.samples, report.warm_up, report.source
+    );
+    println!(
+        "day  part  {:>12}  {:>12}  {:>12}  {:>12}",
+        "parse", "± std dev", "solve", "± std dev"
+    );
+    for m in &report.results {
+        println!(
+            "{:>3}  {:>4}  {:>12}  {:>12}  {:>12}  {:>12}",
+            m.day,
+            m.part,
+            format_nanos(m.parse.median),
+            format_nanos(m.parse.std_dev),
+            format_nanos(m.solve.median),
+            format_nanos(m.solve.std_dev)
+        );
+    }
+}
+
+pub fn print_changes(changes: &[Change]) {
+    println!(
+        "day  part  step   {:>12}  {:>12}  {:>8}",
+        "before", "after", "change"
+    );
+    for c in changes {
+        println!(
+            "{:>3}  {:>4}  {}  {:>12}  {:>12}  {:>+7.1}%{}",
+            c.day,
+            c.part,
+            c.step,
+            format_nanos(c.before),
+            format_nanos(c.after),
+            c.change * 100.0,
+            if c.regressed { "  SLOWER" } else { "" }
+        );
+    }
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    fn stats(median: u64) -> Stats {
+        Stats {
+            samples: 1,
+            min: median,
+            median,
+            mean: median,
+            std_dev: 0,
+        }
+    }
+
+    fn report(parse: u64, solve: u64) -> Report {
+        Report {
+            source: InputSource::Real,
+            warm_up: 0,
+            samples: 1,
+            results: vec![Measurement {
+                day: 1,
+                part: 1,
+                parse: stats(parse),
+                solve: stats(solve),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+        let stats = Stats::from_samples(&samples);
+        assert_eq!((stats.samples, stats.min, stats.median), (4, 1, 2));
+        assert_eq!((stats.mean, stats.std_dev), (3, 1));
+
+        assert_eq!(Stats::from_samples(&samples[..3]).median, 3);
+    }
+
+    #[test]
+    fn test_measure() {
+        let day01 = registry::find(1).unwrap();
+        let measurement = measure(day01, Part::One, "1\n2\n\n3\n", 1, 3)
+            .unwrap()
+            .unwrap();
+        assert_eq!((measurement.day, measurement.part), (1, 1));
+        assert_eq!(measurement.solve.samples, 3);
+
+        let day25 = registry::find(25).unwrap();
+        assert_eq!(measure(day25, Part::Two, "1\n", 1, 3), Ok(None));
+        assert!(measure(day01, Part::One, "x\n", 1, 3).is_err());
+    }
+
+    #[test]
+    fn test_compare() {
+        let changes = compare(&report(1_000, 1_000_000), &report(2_000, 1_500_000), 0.1).unwrap();
+        assert_eq!(changes.len(), 2);
+        // parsing doubled, but it's so quick that's just noise
+        assert_eq!(changes[0].step, "parse");
+        assert!(!changes[0].regressed);
+        assert_eq!(changes[1].change, 0.5);
+        assert!(changes[1].regressed);
+
+        let changes = compare(&report(1_000, 1_000_000), &report(1_000, 900_000), 0.1).unwrap();
+        assert!(!changes[1].regressed);
+    }
+
+    #[test]
+    fn test_compare_different_inputs() {
+        let synthetic = |size, seed| Report {
+            source: InputSource::Synthetic { size, seed },
+            ..report(1_000, 1_000_000)
+        };
+        assert!(compare(&synthetic(30, 2022), &synthetic(30, 2022), 0.1).is_ok());
+
+        let error = compare(&report(1_000, 1_000_000), &synthetic(30, 2022), 0.1).unwrap_err();
+        assert_eq!(
+            error,
+            "the runs timed different inputs - the real inputs before, \
+             and made-up inputs of size 30 (seed 2022) after"
+        );
+        assert!(compare(&synthetic(30, 2022), &synthetic(30, 1), 0.1).is_err());
+        assert!(compare(&synthetic(30, 2022), &synthetic(40, 2022), 0.1).is_err());
+    }
+
+    #[test]
+    fn test_report_json() {
+        let report = Report {
+            source: InputSource::Synthetic {
+                size: 30,
+                seed: 2022,
+            },
+            ..report(1_000, 1_000_000)
+        };
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(json.contains(r#""source":{"kind":"synthetic","size":30,"seed":2022}"#));
+        assert_eq!(serde_json::from_str::<Report>(&json).unwrap(), report);
+    }
+}
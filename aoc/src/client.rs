@@ -0,0 +1,244 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{input, Part};
+
+pub const YEAR: u16 = 2022;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// the site asks automated tools to say who they are
+const USER_AGENT: &str = "advent-of-code-2022 aoc runner (ureq)";
+
+#[derive(Debug)]
+pub enum ClientError {
+    // the site only hands out inputs (and takes answers) for a logged in user
+    NoSession,
+    Status(u16, String),
+    Transport(String),
+    Cache(PathBuf, io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token - pass --session or set AOC_SESSION to the `session` cookie from the site"
+            ),
+            ClientError::Status(status, body) => {
+                write!(f, "the site said {status}: {}", body.trim())
+            }
+            ClientError::Transport(e) => write!(f, "couldn't talk to the site: {e}"),
+            ClientError::Cache(path, e) => write!(f, "couldn't cache {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+// what the site made of a submitted answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    // sometimes it says whether it was too high or too low
+    Wrong { hint: Option<String> },
+    RateLimited { wait: Option<String> },
+    // the part's already been solved, so it isn't taking answers
+    AlreadySolved,
+    // a page we don't know how to read - the text's kept so it can be shown
+    Unrecognised(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "that's the right answer"),
+            Outcome::Wrong { hint: Some(hint) } => {
+                write!(f, "that's not the right answer - it's {hint}")
+            }
+            Outcome::Wrong { hint: None } => write!(f, "that's not the right answer"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "answered too recently - try again in {wait}")
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "answered too recently"),
+            Outcome::AlreadySolved => write!(f, "that part's already solved"),
+            Outcome::Unrecognised(text) => write!(f, "unexpected response: {text}"),
+        }
+    }
+}
+
+// the answer page is HTML, but the sentences in it are all we need
+pub fn parse_outcome(body: &str) -> Outcome {
+    if body.contains("That's the right answer") {
+        Outcome::Correct
+    } else if body.contains("That's not the right answer") {
+        let hint = ["too high", "too low"]
+            .into_iter()
+            .find(|hint| body.contains(hint))
+            .map(|hint| hint.to_string());
+        Outcome::Wrong { hint }
+    } else if body.contains("You gave an answer too recently") {
+        // "... You have 4m 30s left to wait."
+        let wait = body
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .map(|(wait, _)| wait.to_string());
+        Outcome::RateLimited { wait }
+    } else if body.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        let text = body.split_whitespace().collect::<Vec<_>>().join(" ");
+        Outcome::Unrecognised(text.chars().take(200).collect())
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    // inputs are kept under here, in a directory per year and day
+    cache: PathBuf,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>, cache: &Path) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            cache: cache.to_owned(),
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    pub fn cache_path(&self, day: u8) -> PathBuf {
+        input::path_in(&self.cache, YEAR, day)
+    }
+
+    fn cookie(&self) -> Result<String, ClientError> {
+        let session = self.session.as_deref().ok_or(ClientError::NoSession)?;
+        Ok(format!("session={session}"))
+    }
+
+    // the day's input, from the cache if it's been downloaded before. inputs never
+    // change, so once it's cached this doesn't need the network (or a session) at all
+    pub fn input(&self, day: u8) -> Result<String, ClientError> {
+        let path = self.cache_path(day);
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+
+        let input = self.download(day)?;
+        let cached =
+            fs::create_dir_all(path.parent().unwrap()).and_then(|_| fs::write(&path, &input));
+        cached.map_err(|e| ClientError::Cache(path, e))?;
+        Ok(input)
+    }
+
+    pub fn download(&self, day: u8) -> Result<String, ClientError> {
+        let url = format!("{}/{YEAR}/day/{day}/input", self.base_url);
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie()?)
+            .call()
+            .map_err(from_ureq)?;
+        response
+            .into_string()
+            .map_err(|e| ClientError::Transport(e.to_string()))
+    }
+
+    pub fn submit(&self, day: u8, part: Part, answer: &str) -> Result<Outcome, ClientError> {
+        let url = format!("{}/{YEAR}/day/{day}/answer", self.base_url);
+        let level = part.number().to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie()?)
+            .send_form(&[("level", &level), ("answer", answer)])
+            .map_err(from_ureq)?;
+        let body = response
+            .into_string()
+            .map_err(|e| ClientError::Transport(e.to_string()))?;
+        Ok(parse_outcome(&body))
+    }
+}
+
+fn from_ureq(error: ureq::Error) -> ClientError {
+    match error {
+        ureq::Error::Status(status, response) => {
+            ClientError::Status(status, response.into_string().unwrap_or_default())
+        }
+        ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockSite;
+
+    #[test]
+    fn test_parse_outcome() {
+        assert_eq!(
+            parse_outcome("<p>That's the right answer!  You are one gold star closer</p>"),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_outcome("<p>That's not the right answer; your answer is too high.</p>"),
+            Outcome::Wrong {
+                hint: Some("too high".to_string())
+            }
+        );
+        assert_eq!(
+            parse_outcome("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. </p>"),
+            Outcome::RateLimited {
+                wait: Some("34s".to_string())
+            }
+        );
+        assert_eq!(
+            parse_outcome("<p>You don't seem to be solving the right level.  Did you already complete it?</p>"),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            parse_outcome("<html>\n  <p>Something else</p>\n</html>"),
+            Outcome::Unrecognised("<html> <p>Something else</p> </html>".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fetch_and_submit() {
+        let cache = std::env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+        let mut site = MockSite::default();
+        site.add_input(1, "1000\n2000\n\n3000\n");
+        site.add_answer(1, Part::One, "3000");
+        let base_url = site.spawn().unwrap();
+
+        let no_session = Client::new(&base_url, None, &cache);
+        assert!(matches!(no_session.input(1), Err(ClientError::NoSession)));
+
+        let client = Client::new(&base_url, Some("token".to_string()), &cache);
+        assert!(matches!(client.input(2), Err(ClientError::Status(404, _))));
+        assert_eq!(client.input(1).unwrap(), "1000\n2000\n\n3000\n");
+        assert!(client.cache_path(1).ends_with("2022/day01/input.txt"));
+
+        // once it's cached, the site isn't needed any more
+        let offline = Client::new("http://127.0.0.1:1", None, &cache);
+        assert_eq!(offline.input(1).unwrap(), "1000\n2000\n\n3000\n");
+
+        assert_eq!(
+            client.submit(1, Part::One, "2000").unwrap(),
+            Outcome::Wrong {
+                hint: Some("too low".to_string())
+            }
+        );
+        assert!(matches!(
+            client.submit(1, Part::One, "3000").unwrap(),
+            Outcome::RateLimited { .. }
+        ));
+
+        fs::remove_dir_all(&cache).unwrap();
+    }
+}
@@ -0,0 +1,84 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use aoc_common::{params, Overrides};
+
+// a config file has a table of parameters for each day it changes, like
+//
+//   [day15]
+//   row = 10
+//   search_limit = 20
+pub fn parse_config(text: &str) -> Result<HashMap<u8, Overrides>, String> {
+    let table = text.parse::<toml::Table>().map_err(|e| e.to_string())?;
+    let mut days = HashMap::new();
+    for (name, value) in table {
+        let day = name
+            .strip_prefix("day")
+            .and_then(|d| d.parse::<u8>().ok())
+            .filter(|d| (1..=25).contains(d))
+            .ok_or_else(|| format!("`[{name}]` should be a day, like `[day15]`"))?;
+        let toml::Value::Table(overrides) = value else {
+            return Err(format!("`{name}` should be a table of parameters"));
+        };
+        days.insert(day, overrides);
+    }
+    Ok(days)
+}
+
+// the changes to make to a day's parameters - from the config file if there
+// is one, and then from the command line, which wins if they both change something
+pub fn overrides_for(
+    day: u8,
+    config: Option<&Path>,
+    params: &[String],
+) -> Result<Overrides, String> {
+    let mut overrides = match config {
+        Some(path) => {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+            parse_config(&text)
+                .map_err(|e| format!("in {}: {e}", path.display()))?
+                .remove(&day)
+                .unwrap_or_default()
+        }
+        None => Overrides::new(),
+    };
+
+    for param in params {
+        let (name, value) = params::parse_override(param)?;
+        overrides.insert(name, value);
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = parse_config("[day15]\nrow = 10\nsearch_limit = 20\n\n[day7]\n").unwrap();
+        assert_eq!(config[&15]["row"].as_integer(), Some(10));
+        assert!(config[&7].is_empty());
+
+        assert!(parse_config("[day26]\n").is_err());
+        assert!(parse_config("[fifteen]\n").is_err());
+        assert!(parse_config("day15 = 10\n").is_err());
+    }
+
+    #[test]
+    fn test_command_line_wins() {
+        let path = std::env::temp_dir().join(format!("aoc-config-{}.toml", std::process::id()));
+        fs::write(&path, "[day15]\nrow = 10\nsearch_limit = 20\n").unwrap();
+
+        let from_config = overrides_for(15, Some(&path), &[]).unwrap();
+        assert_eq!(from_config["row"].as_integer(), Some(10));
+        // the same param again on the command line replaces the config's one,
+        // and the ones it doesn't mention are left alone
+        let overrides = overrides_for(15, Some(&path), &["row=2000000".to_string()]).unwrap();
+        assert_eq!(overrides["row"].as_integer(), Some(2_000_000));
+        assert_eq!(overrides["search_limit"].as_integer(), Some(20));
+        assert!(overrides_for(15, Some(&path), &["row".to_string()]).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,568 @@
+use std::{
+    fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    process,
+};
+
+use aoc_common::{input, Overrides, Part, RunError};
+use clap::{Args, Parser, Subcommand};
+use client::{Client, Outcome};
+use mock::MockSite;
+use output::{Format, Record};
+
+mod bench;
+mod client;
+mod config;
+mod mock;
+mod output;
+mod registry;
+mod verify;
+mod viewer;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Parse and solve a day's puzzle, timing each step
+    Run {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Only run this part (by default both parts are run)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input file - read from stdin if this isn't given
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        #[command(flatten)]
+        params: ParamArgs,
+        /// Print answers for people, or as one JSON object per part for scripts
+        #[arg(short, long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Show the parameters a day would be solved with
+    Params {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        params: ParamArgs,
+    },
+    /// Check every day's answers against the ones recorded in an answers file, and the examples
+    Verify {
+        /// Only check this day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// The answers for real inputs - input paths in it, and the days' examples, are relative to this file
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+        /// Fail if any part couldn't be checked, instead of just warning
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Time parsing and solving each day's puzzle over repeated runs
+    Bench {
+        /// Only benchmark this day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Directory of puzzle inputs, laid out like `2022/day01/input.txt` - days without one are skipped
+        #[arg(short, long, default_value = input::DIR)]
+        inputs: PathBuf,
+        /// Runs to do first that aren't timed
+        #[arg(short, long, default_value_t = 3)]
+        warm_up: usize,
+        /// Timed runs of each part
+        #[arg(short, long, default_value_t = 10)]
+        samples: usize,
+        /// Write the results here as JSON, to compare with another run later
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Make up inputs of this size to time, rather than reading the real ones
+        #[arg(long, value_name = "SIZE")]
+        synthetic: Option<usize>,
+        /// The seed for the made-up inputs
+        #[arg(long, default_value_t = 2022)]
+        seed: u64,
+    },
+    /// Make up a puzzle input for a day and print it, to try the solvers on bigger ones
+    Generate {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// How big to make it - what this counts depends on the day, like elves,
+        /// sensors or valves
+        #[arg(short, long)]
+        size: usize,
+        /// The same seed always makes the same input
+        #[arg(long, default_value_t = 2022)]
+        seed: u64,
+    },
+    /// Compare two sets of benchmark results, and fail if anything got slower
+    Compare {
+        before: PathBuf,
+        after: PathBuf,
+        /// How much slower (as a percentage) counts as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+    },
+    /// Download a day's puzzle input into the cache, and print where it went
+    Fetch {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Submit an answer - if it isn't given, solve the day's (fetched) input and submit that
+    Submit {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+        #[command(flatten)]
+        site: SiteArgs,
+    },
+    /// Watch one of the simulation days play out, or dump its frames to files
+    View {
+        /// One of the days that simulates something: 5, 9, 10, 14, 17, 23 or 24
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input file - read from stdin if this isn't given
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Frames a second to start with (+/- change it while it's playing)
+        #[arg(long, default_value_t = 10.0)]
+        fps: f64,
+        /// Only show every Nth step
+        #[arg(long, default_value_t = 1)]
+        every: usize,
+        /// Stop after this many steps - some simulations would go on forever
+        #[arg(long, default_value_t = 10_000)]
+        steps: usize,
+        /// Write the frames to text files in this directory, rather than playing them
+        #[arg(long)]
+        dump: Option<PathBuf>,
+    },
+    /// Pretend to be the puzzle site, serving the inputs and answers from an answers file (or the examples)
+    MockServer {
+        #[arg(long, default_value_t = 8022)]
+        port: u16,
+        #[arg(short, long, default_value = "answers.toml")]
+        answers: PathBuf,
+    },
+}
+
+// where to get inputs from (and send answers to)
+#[derive(Args)]
+struct SiteArgs {
+    /// The site to talk to - point it at `aoc mock-server` to try things out
+    #[arg(long, env = "AOC_BASE_URL", default_value = client::DEFAULT_BASE_URL)]
+    base_url: String,
+    /// The `session` cookie from logging in to the site
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// Downloaded inputs are kept here, like `inputs/2022/day01/input.txt`
+    #[arg(long, default_value = input::DIR)]
+    cache: PathBuf,
+}
+
+impl SiteArgs {
+    fn client(self) -> Client {
+        Client::new(&self.base_url, self.session, &self.cache)
+    }
+}
+
+// changes to the puzzle's parameters, for running it on something other than the real puzzle
+#[derive(Args)]
+struct ParamArgs {
+    /// Change one of the day's parameters, like `--param row=10` (can be repeated)
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    params: Vec<String>,
+    /// TOML file with a table of parameters for each day, like `[day15]` - any
+    /// `--param`s are applied on top
+    #[arg(long)]
+    config: Option<PathBuf>,
+}
+
+impl ParamArgs {
+    fn overrides(&self, day: u8) -> Result<Overrides, String> {
+        config::overrides_for(day, self.config.as_deref(), &self.params)
+    }
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    params: ParamArgs,
+    format: Format,
+) -> Result<(), String> {
+    let solution = registry::find(day).ok_or_else(|| format!("day {day} isn't solved yet"))?;
+    let overrides = params.overrides(day)?;
+    let puzzle_input = input::load(input.as_deref()).map_err(|e| e.to_string())?;
+    let parts = match part {
+        Some(p) => vec![Part::try_from(p).map_err(|p| format!("there's no part {p}"))?],
+        None => Part::both().to_vec(),
+    };
+
+    for part in parts {
+        let result = solution.run(part, &puzzle_input, &overrides);
+        // the details of what went wrong always go to stderr, so scripts only see the records
+        if format == Format::Json {
+            match &result {
+                Some(Ok(result)) => Record::answer(day, part, result),
+                Some(Err(e)) => Record::error(day, part, e.to_string()),
+                None => Record::no_puzzle(day, part),
+            }
+            .print();
+        }
+
+        match result {
+            Some(Err(RunError::Params(e))) => return Err(format!("day {day}: {e}")),
+            Some(Err(RunError::Parse(e))) => {
+                eprintln!(
+                    "{}",
+                    e.diagnostic(&input::Source::from_path(input.as_deref()))
+                );
+                eprintln!();
+                return Err(format!("couldn't parse the input for day {day}"));
+            }
+            _ if format == Format::Json => {}
+            Some(Ok(result)) => {
+                println!("Day {day}, part {part}: {}", result.answer);
+                println!("    parse: {:?}", result.parse_time);
+                println!("    solve: {:?}", result.solve_time);
+                if !result.stats.is_empty() {
+                    println!("    stats:");
+                    for (name, count) in &result.stats {
+                        println!("        {name}: {count}");
+                    }
+                }
+            }
+            None => println!("Day {day}, part {part}: no puzzle"),
+        }
+    }
+
+    Ok(())
+}
+
+fn show_params(day: u8, params: ParamArgs) -> Result<(), String> {
+    let solution = registry::find(day).ok_or_else(|| format!("day {day} isn't solved yet"))?;
+    let params = solution
+        .params(&params.overrides(day)?)
+        .map_err(|e| format!("day {day}: {e}"))?;
+    if params.is_empty() {
+        println!("day {day} doesn't have any parameters");
+    } else {
+        print!("{params}");
+    }
+    Ok(())
+}
+
+// the answers recorded for real inputs, then the days' examples next to the
+// answers file - which have their own answers in them
+fn read_answers(answers: &Path) -> Result<Vec<verify::Answer>, String> {
+    let text = fs::read_to_string(answers)
+        .map_err(|e| format!("couldn't read {}: {e}", answers.display()))?;
+    let mut recorded =
+        verify::parse_answers(&text).map_err(|e| format!("in {}: {e}", answers.display()))?;
+    recorded.extend(verify::examples(
+        answers.parent().unwrap_or(Path::new(".")),
+    )?);
+    Ok(recorded)
+}
+
+fn verify(day: Option<u8>, answers: PathBuf, strict: bool) -> Result<(), String> {
+    let recorded = read_answers(&answers)?;
+    let base = answers.parent().unwrap_or(Path::new("."));
+
+    let rows = verify::verify(&recorded, base, day);
+    verify::print_table(&rows);
+
+    let failures = rows.iter().filter(|r| r.status.is_failure()).count();
+    if failures > 0 {
+        return Err(format!(
+            "{failures} of the recorded answers didn't check out"
+        ));
+    }
+    let missing = rows.iter().filter(|r| r.status.is_missing()).count();
+    if missing > 0 {
+        if strict {
+            return Err(format!("{missing} parts couldn't be checked"));
+        }
+        eprintln!("warning: {missing} parts couldn't be checked");
+    }
+    Ok(())
+}
+
+fn bench(
+    day: Option<u8>,
+    inputs: PathBuf,
+    warm_up: usize,
+    samples: usize,
+    output: Option<PathBuf>,
+    synthetic: Option<usize>,
+    seed: u64,
+) -> Result<(), String> {
+    if samples == 0 {
+        return Err("there has to be at least one sample".to_string());
+    }
+    if synthetic == Some(0) {
+        return Err("--synthetic has to be at least 1".to_string());
+    }
+
+    let mut results = Vec::new();
+    for solution in registry::all() {
+        let day_number = solution.day();
+        if day.is_some_and(|d| d != day_number) {
+            continue;
+        }
+
+        let path = input::path_in(&inputs, client::YEAR, day_number);
+        let puzzle_input = match synthetic {
+            Some(size) => solution.generate(seed, size),
+            None => fs::read_to_string(&path).ok(),
+        };
+        let Some(puzzle_input) = puzzle_input else {
+            match synthetic {
+                Some(_) => eprintln!("skipping day {day_number}: it can't make up inputs"),
+                None => eprintln!(
+                    "skipping day {day_number}: couldn't read {}",
+                    path.display()
+                ),
+            }
+            continue;
+        };
+
+        for part in Part::both() {
+            match bench::measure(solution, part, &puzzle_input, warm_up, samples) {
+                Ok(Some(measurement)) => results.push(measurement),
+                Ok(None) => {}
+                Err(RunError::Params(e)) => return Err(format!("day {day_number}: {e}")),
+                Err(RunError::Parse(e)) if synthetic.is_some() => {
+                    return Err(format!(
+                        "day {day_number}'s made-up input didn't parse: {e}"
+                    ));
+                }
+                Err(RunError::Parse(e)) => {
+                    eprintln!("{}", e.diagnostic(&input::Source::File(path)));
+                    eprintln!();
+                    return Err(format!("couldn't parse the input for day {day_number}"));
+                }
+            }
+        }
+    }
+
+    let report = bench::Report {
+        source: match synthetic {
+            Some(size) => bench::InputSource::Synthetic { size, seed },
+            None => bench::InputSource::Real,
+        },
+        warm_up,
+        samples,
+        results,
+    };
+    bench::print_report(&report);
+
+    if let Some(output) = output {
+        let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+        fs::write(&output, json + "\n")
+            .map_err(|e| format!("couldn't write {}: {e}", output.display()))?;
+    }
+    Ok(())
+}
+
+fn generate(day: u8, size: usize, seed: u64) -> Result<(), String> {
+    if size == 0 {
+        return Err("--size has to be at least 1".to_string());
+    }
+    let solution = registry::find(day).ok_or_else(|| format!("day {day} isn't solved yet"))?;
+    let input = solution
+        .generate(seed, size)
+        .ok_or_else(|| format!("day {day} can't make up inputs"))?;
+    print!("{input}");
+    Ok(())
+}
+
+fn read_report(path: &Path) -> Result<bench::Report, String> {
+    let text =
+        fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+    serde_json::from_str(&text).map_err(|e| format!("in {}: {e}", path.display()))
+}
+
+fn compare(before: PathBuf, after: PathBuf, threshold: f64) -> Result<(), String> {
+    let changes = bench::compare(
+        &read_report(&before)?,
+        &read_report(&after)?,
+        threshold / 100.0,
+    )?;
+    bench::print_changes(&changes);
+
+    let regressions = changes.iter().filter(|c| c.regressed).count();
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} steps got more than {threshold}% slower"
+        ));
+    }
+    Ok(())
+}
+
+fn fetch(day: u8, site: SiteArgs) -> Result<(), String> {
+    let client = site.client();
+    client.input(day).map_err(|e| e.to_string())?;
+    println!("{}", client.cache_path(day).display());
+    Ok(())
+}
+
+fn submit(day: u8, part: u8, answer: Option<String>, site: SiteArgs) -> Result<(), String> {
+    let part = Part::try_from(part).map_err(|p| format!("there's no part {p}"))?;
+    let client = site.client();
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution =
+                registry::find(day).ok_or_else(|| format!("day {day} isn't solved yet"))?;
+            let input = client.input(day).map_err(|e| e.to_string())?;
+            match solution.run(part, &input, &Overrides::new()) {
+                Some(Ok(result)) => result.answer,
+                Some(Err(e)) => return Err(format!("day {day}: {e}")),
+                None => return Err(format!("day {day} has no part {part}")),
+            }
+        }
+    };
+
+    println!("Day {day}, part {part}: submitting {answer}");
+    let outcome = client
+        .submit(day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    println!("{outcome}");
+    match outcome {
+        Outcome::Correct | Outcome::AlreadySolved => Ok(()),
+        _ => Err("the answer wasn't accepted".to_string()),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn view(
+    day: u8,
+    part: u8,
+    input: Option<PathBuf>,
+    fps: f64,
+    every: usize,
+    steps: usize,
+    dump: Option<PathBuf>,
+) -> Result<(), String> {
+    let part = Part::try_from(part).map_err(|p| format!("there's no part {p}"))?;
+    if every == 0 {
+        return Err("--every has to be at least 1".to_string());
+    }
+    if fps.is_nan() || fps <= 0.0 {
+        return Err("--fps has to be more than 0".to_string());
+    }
+    let puzzle_input = input::load(input.as_deref()).map_err(|e| e.to_string())?;
+
+    let shown = viewer::with_simulation(day, part, &puzzle_input, |simulation| match &dump {
+        Some(dir) => viewer::dump(simulation, dir, every, steps).map(|frames| {
+            println!("wrote {frames} frames to {}", dir.display());
+        }),
+        None => viewer::play(
+            simulation,
+            &viewer::Playback {
+                title: format!("day {day} part {part}"),
+                fps,
+                every,
+                max_steps: steps,
+            },
+        ),
+    });
+
+    match shown {
+        Ok(Some(result)) => result.map_err(|e| e.to_string()),
+        Ok(None) => Err(format!(
+            "day {day} doesn't simulate anything - try one of {:?}",
+            viewer::DAYS
+        )),
+        Err(e) => {
+            eprintln!(
+                "{}",
+                e.diagnostic(&input::Source::from_path(input.as_deref()))
+            );
+            eprintln!();
+            Err(format!("couldn't parse the input for day {day}"))
+        }
+    }
+}
+
+fn mock_server(port: u16, answers: PathBuf) -> Result<(), String> {
+    let recorded = read_answers(&answers)?;
+    let site = MockSite::from_answers(&recorded, answers.parent().unwrap_or(Path::new(".")));
+
+    let listener = TcpListener::bind(("127.0.0.1", port)).map_err(|e| e.to_string())?;
+    eprintln!(
+        "serving days {:?} on http://127.0.0.1:{port} - any session token will do",
+        site.days()
+    );
+    site.serve(listener);
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            params,
+            format,
+        } => run(day, part, input, params, format),
+        Command::Params { day, params } => show_params(day, params),
+        Command::Verify {
+            day,
+            answers,
+            strict,
+        } => verify(day, answers, strict),
+        Command::Bench {
+            day,
+            inputs,
+            warm_up,
+            samples,
+            output,
+            synthetic,
+            seed,
+        } => bench(day, inputs, warm_up, samples, output, synthetic, seed),
+        Command::Generate { day, size, seed } => generate(day, size, seed),
+        Command::Compare {
+            before,
+            after,
+            threshold,
+        } => compare(before, after, threshold),
+        Command::Fetch { day, site } => fetch(day, site),
+        Command::Submit {
+            day,
+            part,
+            answer,
+            site,
+        } => submit(day, part, answer, site),
+        Command::View {
+            day,
+            part,
+            input,
+            fps,
+            every,
+            steps,
+            dump,
+        } => view(day, part, input, fps, every, steps, dump),
+        Command::MockServer { port, answers } => mock_server(port, answers),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use aoc_common::Part;
+
+use crate::{client::YEAR, verify::Answer};
+
+// a stand-in for the puzzle site, just enough of it to fetch inputs and submit
+// answers against, so the client can be tried out without a real account
+pub struct MockSite {
+    inputs: HashMap<u8, String>,
+    answers: HashMap<(u8, Part), String>,
+    // how long a wrong answer locks out any more submissions
+    pub cooldown: Duration,
+}
+
+impl Default for MockSite {
+    fn default() -> Self {
+        Self {
+            inputs: HashMap::new(),
+            answers: HashMap::new(),
+            cooldown: Duration::from_secs(60),
+        }
+    }
+}
+
+// what the site remembers between requests
+#[derive(Default)]
+struct State {
+    solved: HashSet<(u8, Part)>,
+    last_wrong: Option<Instant>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    session: Option<String>,
+    body: String,
+}
+
+impl MockSite {
+    pub fn add_input(&mut self, day: u8, input: &str) {
+        self.inputs.insert(day, input.to_string());
+    }
+
+    pub fn add_answer(&mut self, day: u8, part: Part, answer: &str) {
+        self.answers.insert((day, part), answer.to_string());
+    }
+
+    // serve the inputs and answers recorded in an answers file. only the first
+    // readable input for each day is used, and ones with params (like the
+    // examples) aren't real inputs, so they're left out
+    pub fn from_answers(answers: &[Answer], base: &Path) -> Self {
+        let mut site = Self::default();
+        for answer in answers.iter().filter(|a| a.params.is_empty()) {
+            if site.inputs.contains_key(&answer.day) {
+                continue;
+            }
+            let Ok(input) = answer.read_input(base) else {
+                continue;
+            };
+            site.add_input(answer.day, &input);
+            for part in Part::both() {
+                if let Some(expected) = answer.expected(part) {
+                    site.add_answer(answer.day, part, &expected);
+                }
+            }
+        }
+        site
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        let mut days = self.inputs.keys().copied().collect::<Vec<_>>();
+        days.sort();
+        days
+    }
+
+    // start serving on a free local port in the background, and return the base URL
+    #[cfg(test)]
+    pub fn spawn(self) -> io::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let base_url = format!("http://{}", listener.local_addr()?);
+        std::thread::spawn(move || self.serve(listener));
+        Ok(base_url)
+    }
+
+    // handle requests one at a time, forever
+    pub fn serve(self, listener: TcpListener) {
+        let mut state = State::default();
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let (status, body) = match read_request(&mut stream) {
+                Ok(request) => self.respond(&mut state, &request),
+                Err(e) => (400, format!("Bad request: {e}")),
+            };
+            // the client hanging up early isn't the site's problem
+            let _ = write_response(&mut stream, status, &body);
+        }
+    }
+
+    fn respond(&self, state: &mut State, request: &Request) -> (u16, String) {
+        if request.session.as_deref().unwrap_or("").is_empty() {
+            return (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.".into(),
+            );
+        }
+
+        let segments = request.path.split('/').collect::<Vec<_>>();
+        let route = match segments[..] {
+            ["", year, "day", day, endpoint] if year == YEAR.to_string() => {
+                day.parse::<u8>().ok().map(|day| (day, endpoint))
+            }
+            _ => None,
+        };
+        match (request.method.as_str(), route) {
+            ("GET", Some((day, "input"))) => match self.inputs.get(&day) {
+                Some(input) => (200, input.clone()),
+                None => (404, "404 Not Found".into()),
+            },
+            ("POST", Some((day, "answer"))) => self.submit(state, day, &request.body),
+            _ => (404, "404 Not Found".into()),
+        }
+    }
+
+    fn submit(&self, state: &mut State, day: u8, form: &str) -> (u16, String) {
+        let field = |name| {
+            form.split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| url_decode(value))
+        };
+        let part = match field("level").as_deref() {
+            Some("1") => Part::One,
+            Some("2") => Part::Two,
+            _ => return (400, "Bad request: level should be 1 or 2".into()),
+        };
+        let Some(answer) = field("answer") else {
+            return (400, "Bad request: no answer".into());
+        };
+        let Some(expected) = self.answers.get(&(day, part)) else {
+            return (404, "404 Not Found".into());
+        };
+
+        let page = |text: &str| (200, format!("<article><p>{text}</p></article>"));
+        if state.solved.contains(&(day, part)) {
+            return page(
+                "You don't seem to be solving the right level.  Did you already complete it?",
+            );
+        }
+        if let Some(waited) = state.last_wrong.map(|t| t.elapsed()) {
+            if waited < self.cooldown {
+                let left = (self.cooldown - waited).as_secs().max(1);
+                return page(&format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {left}s left to wait."));
+            }
+        }
+
+        if answer == *expected {
+            state.solved.insert((day, part));
+            return page("That's the right answer!  You are one gold star closer to collecting enough star fruit.");
+        }
+        state.last_wrong = Some(Instant::now());
+        let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => " your answer is too high.",
+            (Ok(_), Ok(_)) => " your answer is too low.",
+            _ => "",
+        };
+        page(&format!("That's not the right answer;{hint}"))
+    }
+}
+
+fn read_request(stream: &mut TcpStream) -> io::Result<Request> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut words = line.split_whitespace();
+    let (Some(method), Some(path)) = (words.next(), words.next()) else {
+        return Err(invalid("no request line"));
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut session = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader.read_line(&mut header)?;
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(invalid("malformed header"));
+        };
+        let value = value.trim();
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => {
+                session = value
+                    .split(';')
+                    .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .next()
+                    .map(|s| s.to_string())
+            }
+            "content-length" => {
+                content_length = value.parse().map_err(|_| invalid("bad content length"))?
+            }
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request {
+        method,
+        path,
+        session,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+// form values have spaces as `+` and anything unusual (like `=` in day 25's
+// answers) as `%XX`
+fn url_decode(value: &str) -> String {
+    let mut bytes = Vec::new();
+    let mut chars = value.bytes();
+    while let Some(b) = chars.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [chars.next(), chars.next()];
+                let decoded = match hex {
+                    [Some(high), Some(low)] => std::str::from_utf8(&[high, low])
+                        .ok()
+                        .and_then(|h| u8::from_str_radix(h, 16).ok()),
+                    _ => None,
+                };
+                bytes.push(decoded.unwrap_or(b'?'));
+            }
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{Client, Outcome};
+
+    #[test]
+    fn test_url_decode() {
+        assert_eq!(url_decode("2%3D-1%3D0"), "2=-1=0");
+        assert_eq!(url_decode("a+b"), "a b");
+    }
+
+    #[test]
+    fn test_solving() {
+        let mut site = MockSite {
+            cooldown: Duration::ZERO,
+            ..MockSite::default()
+        };
+        site.add_answer(25, Part::One, "2=-1=0");
+        let base_url = site.spawn().unwrap();
+        let client = Client::new(&base_url, Some("token".to_string()), Path::new("unused"));
+
+        assert_eq!(
+            client.submit(25, Part::One, "2=-1=1").unwrap(),
+            Outcome::Wrong { hint: None }
+        );
+        assert_eq!(
+            client.submit(25, Part::One, "2=-1=0").unwrap(),
+            Outcome::Correct
+        );
+        assert_eq!(
+            client.submit(25, Part::One, "2=-1=0").unwrap(),
+            Outcome::AlreadySolved
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+
+use aoc_common::{solution::PartResult, Part};
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    // for people - the answer, then how long each step took
+    #[default]
+    Text,
+    // one JSON object per line for each part, for scripts
+    Json,
+}
+
+// what `--format json` prints for each part. a part either has an answer,
+// has an error, or doesn't exist (day 25 part 2) and has neither
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answer: Option<serde_json::Value>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub answer_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_ns: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    // the solver's counters, when it's built with the `stats` feature
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub stats: BTreeMap<&'static str, u64>,
+}
+
+impl Record {
+    fn empty(day: u8, part: Part) -> Self {
+        Self {
+            day,
+            part: part.number(),
+            answer: None,
+            answer_type: None,
+            parse_ns: None,
+            solve_ns: None,
+            error: None,
+            stats: BTreeMap::new(),
+        }
+    }
+
+    pub fn answer(day: u8, part: Part, result: &PartResult) -> Self {
+        // numbers stay numbers, so nothing has to parse them back out of a string
+        let answer = match result.answer.parse::<i64>() {
+            Ok(n) => serde_json::Value::from(n),
+            Err(_) => serde_json::Value::from(result.answer.clone()),
+        };
+        Self {
+            answer: Some(answer),
+            answer_type: Some(result.answer_type),
+            parse_ns: Some(result.parse_time.as_nanos() as u64),
+            solve_ns: Some(result.solve_time.as_nanos() as u64),
+            stats: result.stats.iter().copied().collect(),
+            ..Self::empty(day, part)
+        }
+    }
+
+    pub fn error(day: u8, part: Part, error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::empty(day, part)
+        }
+    }
+
+    pub fn no_puzzle(day: u8, part: Part) -> Self {
+        Self::empty(day, part)
+    }
+
+    pub fn print(&self) {
+        println!(
+            "{}",
+            serde_json::to_string(self).expect("records are always valid JSON")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_record_json() {
+        let result = PartResult {
+            answer: "24000".to_string(),
+            answer_type: "usize",
+            parse_time: Duration::from_nanos(1_500),
+            solve_time: Duration::from_micros(2),
+            stats: Vec::new(),
+        };
+        assert_eq!(
+            serde_json::to_string(&Record::answer(1, Part::One, &result)).unwrap(),
+            r#"{"day":1,"part":1,"answer":24000,"type":"usize","parse_ns":1500,"solve_ns":2000}"#
+        );
+
+        let result = PartResult {
+            answer: "2=-1=0".to_string(),
+            answer_type: "String",
+            ..result
+        };
+        assert_eq!(
+            Record::answer(25, Part::One, &result).answer,
+            Some(serde_json::Value::from("2=-1=0"))
+        );
+
+        let result = PartResult {
+            stats: vec![("memo hits", 7), ("nodes expanded", 12)],
+            ..result
+        };
+        assert_eq!(
+            serde_json::to_value(Record::answer(19, Part::One, &result)).unwrap()["stats"],
+            serde_json::json!({ "memo hits": 7, "nodes expanded": 12 })
+        );
+
+        assert_eq!(
+            serde_json::to_string(&Record::no_puzzle(25, Part::Two)).unwrap(),
+            r#"{"day":25,"part":2}"#
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use aoc_common::Runnable;
+
+// every day's solution, in order
+pub fn all() -> [&'static dyn Runnable; 25] {
+    [
+        &day01::Day01,
+        &day02::Day02,
+        &day03::Day03,
+        &day04::Day04,
+        &day05::Day05,
+        &day06::Day06,
+        &day07::Day07,
+        &day08::Day08,
+        &day09::Day09,
+        &day10::Day10,
+        &day11::Day11,
+        &day12::Day12,
+        &day13::Day13,
+        &day14::Day14,
+        &day15::Day15,
+        &day16::Day16,
+        &day17::Day17,
+        &day18::Day18,
+        &day19::Day19,
+        &day20::Day20,
+        &day21::Day21,
+        &day22::Day22,
+        &day23::Day23,
+        &day24::Day24,
+        &day25::Day25,
+    ]
+}
+
+pub fn find(day: u8) -> Option<&'static dyn Runnable> {
+    all().into_iter().find(|d| d.day() == day)
+}
+
+#[test]
+fn test_registry_is_complete() {
+    let days = all().iter().map(|d| d.day()).collect::<Vec<_>>();
+    assert_eq!(days, (1..=25).collect::<Vec<_>>());
+    assert!(find(26).is_none());
+}
+
+// every day's made-up inputs should parse and solve. day 19 gets less time, as
+// its search is slow without optimisations even on a couple of blueprints
+#[test]
+fn test_generated_inputs() {
+    use aoc_common::{Overrides, Part};
+
+    for day in all() {
+        let overrides = match day.day() {
+            19 => Overrides::from_iter(
+                ["minutes_part1", "minutes_part2"]
+                    .map(|name| (name.to_string(), toml::Value::Integer(12))),
+            ),
+            _ => Overrides::new(),
+        };
+        let input = day
+            .generate(2022, 4)
+            .unwrap_or_else(|| panic!("day {} can't make up inputs", day.day()));
+        assert_eq!(day.generate(2022, 4), Some(input.clone()));
+        for part in Part::both() {
+            if let Some(Err(e)) = day.run(part, &input, &overrides) {
+                panic!("day {}, part {part}: {e}\n{input}", day.day());
+            }
+        }
+    }
+}
@@ -0,0 +1,464 @@
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc_common::{examples::Example, input, Overrides, Part, Runnable};
+use serde::Deserialize;
+
+use crate::{client::YEAR, registry};
+
+// the answers file is a list of these - one per day and real input file. the
+// input path is relative to the answers file itself. without one, it's the day's
+// input under `inputs/`, where the runner downloads it to. params change the
+// puzzle's parameters for inputs that need different ones. the examples are
+// turned into these as well, with their answers and params out of the fixture
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Answer {
+    pub day: u8,
+    pub input: Option<PathBuf>,
+    pub part1: Option<Expected>,
+    pub part2: Option<Expected>,
+    #[serde(default)]
+    pub params: Overrides,
+}
+
+impl Answer {
+    // a day's example, minus any parts its fixture says to skip
+    pub fn from_example(day: u8, path: PathBuf, example: Example) -> Self {
+        let expected = |answer: Option<String>, ignore: &Option<String>| match ignore {
+            Some(_) => None,
+            None => answer.map(Expected::Text),
+        };
+        Self {
+            day,
+            input: Some(path),
+            part1: expected(example.part1, &example.ignore_part1),
+            part2: expected(example.part2, &example.ignore_part2),
+            params: example.params,
+        }
+    }
+
+    pub fn expected(&self, part: Part) -> Option<String> {
+        let expected = match part {
+            Part::One => self.part1.as_ref(),
+            Part::Two => self.part2.as_ref(),
+        };
+        expected.map(|e| e.to_string())
+    }
+
+    pub fn input_path(&self) -> PathBuf {
+        self.input
+            .clone()
+            .unwrap_or_else(|| input::path_in(Path::new(input::DIR), YEAR, self.day))
+    }
+
+    // the puzzle input - out of the fixture, if it's one of the examples
+    pub fn read_input(&self, base: &Path) -> Result<String, String> {
+        let path = base.join(self.input_path());
+        let text = fs::read_to_string(&path)
+            .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+        if is_example(&path) {
+            let example = Example::parse(&text)
+                .map_err(|e| format!("{} isn't a valid example: {e}", path.display()))?;
+            Ok(example.input)
+        } else {
+            Ok(text)
+        }
+    }
+}
+
+// most answers are numbers, so let them be written without quotes
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Expected {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Number(n) => write!(f, "{n}"),
+            Expected::Text(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AnswerFile {
+    #[serde(default)]
+    answer: Vec<Answer>,
+}
+
+pub fn parse_answers(text: &str) -> Result<Vec<Answer>, String> {
+    let file: AnswerFile = toml::from_str(text).map_err(|e| e.to_string())?;
+    for answer in &file.answer {
+        if registry::find(answer.day).is_none() {
+            return Err(format!("there's no day {} to check", answer.day));
+        }
+        // so there's only the one place to keep an example's answers up to date
+        if let Some(input) = answer.input.as_ref().filter(|i| is_example(i)) {
+            return Err(format!(
+                "{} is an example, which is checked against its own answers already",
+                input.display()
+            ));
+        }
+    }
+    Ok(file.answer)
+}
+
+fn is_example(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "toml")
+}
+
+// every day's examples, from the `dayNN/examples` directories under `base`.
+// days without any are skipped, but a fixture that can't be read is an error
+pub fn examples(base: &Path) -> Result<Vec<Answer>, String> {
+    let mut answers = Vec::new();
+    for solution in registry::all() {
+        let day = solution.day();
+        let dir = PathBuf::from(format!("day{day:02}")).join("examples");
+        let Ok(entries) = fs::read_dir(base.join(&dir)) else {
+            continue;
+        };
+        let mut paths = entries
+            .map(|entry| entry.map(|e| dir.join(e.file_name())))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("couldn't list {}: {e}", dir.display()))?;
+        paths.retain(|path| is_example(path));
+        paths.sort();
+
+        for path in paths {
+            let text = fs::read_to_string(base.join(&path))
+                .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+            let example = Example::parse(&text)
+                .map_err(|e| format!("{} isn't a valid example: {e}", path.display()))?;
+            answers.push(Answer::from_example(day, path, example));
+        }
+    }
+    Ok(answers)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    // either there's no answer recorded, or there's nothing to run it on
+    Missing(String),
+    // the input didn't parse, or the params didn't fit the day
+    Error(String),
+}
+
+impl Status {
+    fn label(&self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail { .. } => "FAIL",
+            Status::Missing(_) => "missing",
+            Status::Error(_) => "ERROR",
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Status::Pass => String::new(),
+            Status::Fail { expected, actual } => format!("expected {expected}, got {actual}"),
+            Status::Missing(reason) => reason.clone(),
+            Status::Error(e) => e.clone(),
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Error(_))
+    }
+
+    pub fn is_missing(&self) -> bool {
+        matches!(self, Status::Missing(_))
+    }
+}
+
+// run one part against its input, and see whether it still gets the right answer
+pub fn check(
+    solution: &dyn Runnable,
+    part: Part,
+    input: &str,
+    expected: &str,
+    overrides: &Overrides,
+) -> Status {
+    match solution.run(part, input, overrides) {
+        Some(Ok(result)) if result.answer == expected => Status::Pass,
+        Some(Ok(result)) => Status::Fail {
+            expected: expected.to_string(),
+            actual: result.answer,
+        },
+        Some(Err(e)) => Status::Error(e.to_string()),
+        None => Status::Missing(format!("day {} has no part {part}", solution.day())),
+    }
+}
+
+pub struct Row {
+    pub day: u8,
+    pub part: Part,
+    pub input: String,
+    pub status: Status,
+}
+
+// check every part of every day (or just the one day) against the answers we know.
+// parts that don't have any answers get a row too, so it's obvious what's not covered
+pub fn verify(answers: &[Answer], base: &Path, only_day: Option<u8>) -> Vec<Row> {
+    let mut rows = Vec::new();
+    for solution in registry::all() {
+        let day = solution.day();
+        if only_day.is_some_and(|d| d != day) {
+            continue;
+        }
+
+        for part in Part::both() {
+            if !solution.has_part(part) {
+                continue;
+            }
+
+            let mut checked = false;
+            for answer in answers.iter().filter(|a| a.day == day) {
+                let Some(expected) = answer.expected(part) else {
+                    continue;
+                };
+                checked = true;
+
+                let status = match answer.read_input(base) {
+                    Ok(input) => check(solution, part, &input, &expected, &answer.params),
+                    Err(e) => Status::Missing(e),
+                };
+                rows.push(Row {
+                    day,
+                    part,
+                    input: answer.input_path().display().to_string(),
+                    status,
+                });
+            }
+
+            if !checked {
+                rows.push(Row {
+                    day,
+                    part,
+                    input: "-".to_string(),
+                    status: Status::Missing("no answer recorded".to_string()),
+                });
+            }
+        }
+    }
+
+    rows
+}
+
+pub fn print_table(rows: &[Row]) {
+    let input_width = rows
+        .iter()
+        .map(|r| r.input.len())
+        .chain(std::iter::once("input".len()))
+        .max()
+        .unwrap();
+
+    println!("day  part  {:input_width$}  result   detail", "input");
+    for row in rows {
+        let line = format!(
+            "{:>3}  {:>4}  {:input_width$}  {:7}  {}",
+            row.day,
+            row.part.number(),
+            row.input,
+            row.status.label(),
+            row.status.detail()
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let count = |label| rows.iter().filter(|r| r.status.label() == label).count();
+    println!();
+    println!(
+        "{} passed, {} failed, {} errors, {} missing",
+        count("pass"),
+        count("FAIL"),
+        count("ERROR"),
+        count("missing")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY01_EXAMPLE: &str =
+        "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    const DAY15_EXAMPLE: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+Sensor at x=9, y=16: closest beacon is at x=10, y=16\n\
+Sensor at x=13, y=2: closest beacon is at x=15, y=3\n\
+Sensor at x=12, y=14: closest beacon is at x=10, y=16\n\
+Sensor at x=10, y=20: closest beacon is at x=10, y=16\n\
+Sensor at x=14, y=17: closest beacon is at x=10, y=16\n\
+Sensor at x=8, y=7: closest beacon is at x=2, y=10\n\
+Sensor at x=2, y=0: closest beacon is at x=2, y=10\n\
+Sensor at x=0, y=11: closest beacon is at x=2, y=10\n\
+Sensor at x=20, y=14: closest beacon is at x=25, y=17\n\
+Sensor at x=17, y=20: closest beacon is at x=21, y=22\n\
+Sensor at x=16, y=7: closest beacon is at x=15, y=3\n\
+Sensor at x=14, y=3: closest beacon is at x=15, y=3\n\
+Sensor at x=20, y=1: closest beacon is at x=15, y=3\n\
+";
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = parse_answers(
+            r#"
+[[answer]]
+day = 25
+input = "inputs/day25.txt"
+part1 = "2=-1=0"
+
+[[answer]]
+day = 1
+part1 = 24000
+part2 = 45000
+
+[[answer]]
+day = 15
+input = "examples/day15.txt"
+part1 = 26
+params = { row = 10 }
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers[0].expected(Part::One).unwrap(), "2=-1=0");
+        assert_eq!(answers[0].expected(Part::Two), None);
+        assert_eq!(answers[1].expected(Part::Two).unwrap(), "45000");
+        assert!(answers[1].params.is_empty());
+        assert_eq!(
+            answers[1].input_path(),
+            Path::new("inputs/2022/day01/input.txt")
+        );
+        assert_eq!(answers[2].input_path(), Path::new("examples/day15.txt"));
+        assert_eq!(answers[2].params["row"].as_integer(), Some(10));
+
+        assert_eq!(parse_answers("").unwrap(), vec![]);
+        assert!(parse_answers("[[answer]]\nday = 26\ninput = \"x\"\n").is_err());
+        assert!(parse_answers("[[answer]]\nday = 1\ninput = \"x\"\npart3 = 1\n").is_err());
+        let error = parse_answers("[[answer]]\nday = 1\ninput = \"day01/examples/example.toml\"\n")
+            .unwrap_err();
+        assert!(error.contains("is an example"), "{error}");
+    }
+
+    #[test]
+    fn test_check() {
+        let day01 = registry::find(1).unwrap();
+        assert_eq!(
+            check(day01, Part::One, DAY01_EXAMPLE, "24000", &Overrides::new()),
+            Status::Pass
+        );
+        assert_eq!(
+            check(day01, Part::One, DAY01_EXAMPLE, "24001", &Overrides::new()),
+            Status::Fail {
+                expected: "24001".to_string(),
+                actual: "24000".to_string()
+            }
+        );
+        assert!(matches!(
+            check(day01, Part::One, "12\nabc\n", "0", &Overrides::new()),
+            Status::Error(_)
+        ));
+
+        let day15 = registry::find(15).unwrap();
+        let unknown = Overrides::from_iter([("rows".to_string(), toml::Value::Integer(10))]);
+        assert!(matches!(
+            check(day15, Part::One, DAY15_EXAMPLE, "26", &unknown),
+            Status::Error(ref e) if e.contains("there's no parameter `rows`")
+        ));
+        let example = Overrides::from_iter([("row".to_string(), toml::Value::Integer(10))]);
+        assert_eq!(
+            check(day15, Part::One, DAY15_EXAMPLE, "26", &example),
+            Status::Pass
+        );
+    }
+
+    #[test]
+    fn test_verify_reports_missing() {
+        let answers = vec![Answer {
+            day: 1,
+            input: Some("no/such/input.txt".into()),
+            part1: Some(Expected::Number(24000)),
+            part2: None,
+            params: Overrides::new(),
+        }];
+        let rows = verify(&answers, Path::new("."), Some(1));
+
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(rows[0].status, Status::Missing(ref r) if r.contains("couldn't read")));
+        assert_eq!(
+            rows[1].status,
+            Status::Missing("no answer recorded".to_string())
+        );
+
+        // day 25 only has the one part
+        assert_eq!(verify(&[], Path::new("."), Some(25)).len(), 1);
+    }
+
+    #[test]
+    fn test_answers_file() {
+        // the one at the top of the workspace, which is just for real inputs
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let text = fs::read_to_string(base.join("answers.toml")).unwrap();
+        parse_answers(&text).unwrap();
+    }
+
+    #[test]
+    fn test_examples() {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let answers = examples(&base).unwrap();
+
+        for day in 1..=25 {
+            assert!(
+                answers.iter().any(|a| a.day == day),
+                "no examples for day {day}"
+            );
+        }
+        for answer in &answers {
+            let input = answer.read_input(&base).unwrap();
+            assert!(
+                !input.is_empty(),
+                "{} is empty",
+                answer.input_path().display()
+            );
+        }
+
+        let day06 = answers.iter().filter(|a| a.day == 6).collect::<Vec<_>>();
+        assert_eq!(day06.len(), 5);
+        assert_eq!(
+            day06[0].input_path(),
+            Path::new("day06/examples/example1.toml")
+        );
+        // the parts the fixtures skip aren't checked
+        let day19 = answers.iter().find(|a| a.day == 19).unwrap();
+        assert_eq!(day19.expected(Part::One).unwrap(), "33");
+        assert_eq!(day19.expected(Part::Two), None);
+
+        let day15 = answers.iter().find(|a| a.day == 15).unwrap();
+        assert_eq!(day15.params["search_limit"].as_integer(), Some(20));
+        let day01 = answers.iter().find(|a| a.day == 1).unwrap();
+        let input = day01.read_input(&base).unwrap();
+        assert_eq!(
+            check(
+                registry::find(1).unwrap(),
+                Part::Two,
+                &input,
+                "45000",
+                &day01.params
+            ),
+            Status::Pass
+        );
+    }
+}
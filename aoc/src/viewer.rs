@@ -0,0 +1,279 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    path::Path,
+    time::Duration,
+};
+
+use aoc_common::{ParseError, Part, Simulation, Solution};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+
+// the days that play something out over time
+pub const DAYS: [u8; 7] = [5, 9, 10, 14, 17, 23, 24];
+
+// set up the day's simulation for this part of the puzzle, and hand it over.
+// None if the day doesn't have one
+pub fn with_simulation<R>(
+    day: u8,
+    part: Part,
+    input: &str,
+    f: impl FnOnce(&mut dyn Simulation) -> R,
+) -> Result<Option<R>, ParseError> {
+    let result = match day {
+        5 => {
+            let input = day05::Day05::parse_part1(input)?;
+            let model = match part {
+                Part::One => day05::Model::CrateMover9000,
+                Part::Two => day05::Model::CrateMover9001,
+            };
+            f(&mut day05::Crane::new(&input, model))
+        }
+        9 => {
+            let input = day09::Day09::parse_part1(input)?;
+            let knots = match part {
+                Part::One => 2,
+                Part::Two => 10,
+            };
+            f(&mut day09::Rope::new(&input, knots))
+        }
+        10 => {
+            let input = day10::Day10::parse_part1(input)?;
+            match part {
+                Part::One => f(&mut day10::Cpu::new(&input)),
+                Part::Two => f(&mut day10::Crt::new(&input)),
+            }
+        }
+        14 => {
+            let cave = day14::Day14::parse_part1(input)?;
+            let bounds = match part {
+                Part::One => cave.bounds(),
+                Part::Two => day14::Bounds::unbounded(),
+            };
+            f(&mut day14::Sandfall::new(&cave, bounds))
+        }
+        17 => {
+            let jets = day17::Day17::parse_part1(input)?;
+            f(&mut day17::Chamber::new(&jets))
+        }
+        23 => {
+            let input = day23::Day23::parse_part1(input)?;
+            f(&mut day23::Grove::new(&input))
+        }
+        24 => f(&mut day24::Day24::parse_part1(input)?),
+        _ => return Ok(None),
+    };
+    Ok(Some(result))
+}
+
+// take up to `every` steps, without going past `max_steps`.
+// false once there's nothing more to see
+fn advance(simulation: &mut dyn Simulation, every: usize, max_steps: usize) -> bool {
+    for _ in 0..every {
+        if simulation.steps() >= max_steps || !simulation.step() {
+            return false;
+        }
+    }
+    true
+}
+
+// write the starting frame, then every `every`th one, to files named after the step
+// they're at. returns how many frames were written
+pub fn dump(
+    simulation: &mut dyn Simulation,
+    dir: &Path,
+    every: usize,
+    max_steps: usize,
+) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    let write_frame = |simulation: &dyn Simulation| {
+        let path = dir.join(format!("frame-{:06}.txt", simulation.steps()));
+        fs::write(path, simulation.render())
+    };
+
+    let mut frames = 0;
+    loop {
+        write_frame(simulation)?;
+        frames += 1;
+
+        let before = simulation.steps();
+        if !advance(simulation, every, max_steps) {
+            // it might have got partway through the next lot before it ended
+            if simulation.steps() != before {
+                write_frame(simulation)?;
+                frames += 1;
+            }
+            return Ok(frames);
+        }
+    }
+}
+
+pub struct Playback {
+    pub title: String,
+    pub fps: f64,
+    pub every: usize,
+    pub max_steps: usize,
+}
+
+// play the simulation in the terminal until it finishes and the viewer's
+// closed. space pauses, n steps on one frame, +/- change the speed and q quits
+pub fn play(simulation: &mut dyn Simulation, playback: &Playback) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    if !stdout.is_terminal() {
+        return Err(io::Error::other(
+            "the viewer needs a terminal - use --dump to write the frames to files instead",
+        ));
+    }
+
+    terminal::enable_raw_mode()?;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+    let result = play_frames(simulation, playback, &mut stdout);
+    // put the terminal back however that went
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+fn play_frames(
+    simulation: &mut dyn Simulation,
+    playback: &Playback,
+    stdout: &mut io::Stdout,
+) -> io::Result<()> {
+    let mut fps = playback.fps;
+    let mut paused = false;
+    let mut finished = false;
+    loop {
+        draw(simulation, playback, fps, paused, finished, stdout)?;
+
+        // wait for a key, or until it's time for the next frame
+        let waiting = paused || finished;
+        let timeout = if waiting {
+            Duration::from_secs(60)
+        } else {
+            Duration::from_secs_f64(1.0 / fps)
+        };
+        if !event::poll(timeout)? {
+            if !waiting {
+                finished = !advance(simulation, playback.every, playback.max_steps);
+            }
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(()),
+            KeyCode::Char(' ') => paused = !paused,
+            KeyCode::Char('n') | KeyCode::Right if !finished => {
+                paused = true;
+                finished = !advance(simulation, playback.every, playback.max_steps);
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Up => fps = (fps * 2.0).min(1000.0),
+            KeyCode::Char('-') | KeyCode::Down => fps = (fps / 2.0).max(0.25),
+            _ => {}
+        }
+    }
+}
+
+fn draw(
+    simulation: &dyn Simulation,
+    playback: &Playback,
+    fps: f64,
+    paused: bool,
+    finished: bool,
+    stdout: &mut io::Stdout,
+) -> io::Result<()> {
+    // only what fits - the top left is usually the interesting bit
+    let (columns, rows) = terminal::size()?;
+    let frame = simulation.render();
+    queue!(
+        stdout,
+        cursor::MoveTo(0, 0),
+        terminal::Clear(ClearType::All)
+    )?;
+    for line in frame.lines().take(rows.saturating_sub(2) as usize) {
+        let line = line.chars().take(columns as usize).collect::<String>();
+        queue!(stdout, Print(line), Print("\r\n"))?;
+    }
+
+    let state = if finished {
+        "finished"
+    } else if paused {
+        "paused"
+    } else {
+        "playing"
+    };
+    let status = format!(
+        "{} | step {} | {fps} fps | {state} | space: pause, n: step, +/-: speed, q: quit",
+        playback.title,
+        simulation.steps()
+    );
+    let status = status.chars().take(columns as usize).collect::<String>();
+    queue!(stdout, Print("\r\n"), Print(status))?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY05_EXAMPLE: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\nmove 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1\nmove 1 from 1 to 2\n";
+
+    #[test]
+    fn test_with_simulation() {
+        let steps = with_simulation(5, Part::One, DAY05_EXAMPLE, |simulation| {
+            while simulation.step() {}
+            simulation.steps()
+        });
+        assert_eq!(steps, Ok(Some(4)));
+
+        assert_eq!(with_simulation(1, Part::One, "1\n", |_| ()), Ok(None));
+        assert!(with_simulation(5, Part::One, "move 1\n", |_| ()).is_err());
+    }
+
+    #[test]
+    fn test_dump() {
+        let dir = std::env::temp_dir().join(format!("aoc-viewer-{}", std::process::id()));
+        let frames = with_simulation(5, Part::Two, DAY05_EXAMPLE, |simulation| {
+            dump(simulation, &dir, 3, 10_000)
+        })
+        .unwrap()
+        .unwrap()
+        .unwrap();
+
+        let mut written = fs::read_dir(&dir)
+            .unwrap()
+            .map(|f| f.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        written.sort();
+        // the start, after 3 steps, and the end partway through the next lot
+        assert_eq!(frames, 3);
+        assert_eq!(
+            written,
+            ["frame-000000.txt", "frame-000003.txt", "frame-000004.txt"]
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("frame-000004.txt")).unwrap(),
+            "        [D]\n        [N]\n        [Z]\n[M] [C] [P]\n 1   2   3\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        // stops at the limit too, even if the simulation would keep going
+        let dir = dir.with_extension("limit");
+        let frames = with_simulation(24, Part::One, "#.###\n#>..#\n###.#\n", |simulation| {
+            dump(simulation, &dir, 1, 5)
+        });
+        assert_eq!(frames.unwrap().unwrap().unwrap(), 6);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
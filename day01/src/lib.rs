@@ -0,0 +1,692 @@
+//! Day 1: Calorie Counting.
+//!
+//! Each [`Elf`] carries a list of snacks; the parts ask for the biggest total
+//! calories one elf is carrying, and the sum of the top three. Both come out of
+//! an [`Inventory`], which also knows which elf is which, where each one ranks,
+//! and how the totals are spread out. [`balance`] shares the snacks out between
+//! a number of carriers as evenly as it can.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fmt::Display,
+    io::{self, BufRead},
+};
+
+pub struct Elf {
+    pub calories: Vec<u32>,
+}
+
+impl Elf {
+    pub fn total_calories(&self) -> u32 {
+        self.calories.iter().sum()
+    }
+}
+
+// an elf is known by where it comes in the input, counting from 0
+pub type ElfId = usize;
+
+// every elf's snacks, along with their totals so they don't keep getting added up
+pub struct Inventory {
+    elves: Vec<Elf>,
+    totals: Vec<u32>,
+}
+
+// how the totals are spread out
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub elves: usize,
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    pub median: f64,
+}
+
+impl Inventory {
+    pub fn new(elves: Vec<Elf>) -> Self {
+        let totals = elves.iter().map(|e| e.total_calories()).collect();
+        Self { elves, totals }
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn total(&self, id: ElfId) -> Option<u32> {
+        self.totals.get(id).copied()
+    }
+
+    // the same order as `TopK`
+    fn order(&self, id: ElfId) -> (u32, Reverse<ElfId>) {
+        (self.totals[id], Reverse(id))
+    }
+
+    // the `n` elves carrying the most, best first
+    pub fn top_n(&self, n: usize) -> Vec<(ElfId, u32)> {
+        let mut top = TopK::new(n);
+        for (id, &total) in self.totals.iter().enumerate() {
+            top.push(id, total);
+        }
+        top.into_sorted()
+    }
+
+    // where an elf comes in the same order as `top_n`, from 1
+    pub fn rank(&self, id: ElfId) -> Option<usize> {
+        self.totals.get(id)?;
+        let order = self.order(id);
+        Some(
+            1 + (0..self.len())
+                .filter(|&other| self.order(other) > order)
+                .count(),
+        )
+    }
+
+    fn sorted_totals(&self) -> Vec<u32> {
+        let mut totals = self.totals.clone();
+        totals.sort_unstable();
+        totals
+    }
+
+    pub fn stats(&self) -> Option<Stats> {
+        let totals = self.sorted_totals();
+        let (&min, &max) = (totals.first()?, totals.last()?);
+        let middle = totals.len() / 2;
+        let median = if totals.len() % 2 == 1 {
+            totals[middle] as f64
+        } else {
+            (totals[middle - 1] as f64 + totals[middle] as f64) / 2.0
+        };
+        Some(Stats {
+            elves: totals.len(),
+            min,
+            max,
+            mean: totals.iter().map(|&t| t as f64).sum::<f64>() / totals.len() as f64,
+            median,
+        })
+    }
+
+    // the smallest total that at least `percent`% of the elves have no more than
+    // (the nearest-rank method, so it's always one of the actual totals)
+    pub fn percentile(&self, percent: f64) -> Option<u32> {
+        assert!(
+            (0.0..=100.0).contains(&percent),
+            "{percent} isn't a percentage"
+        );
+        let totals = self.sorted_totals();
+        let rank = (percent / 100.0 * totals.len() as f64).ceil() as usize;
+        totals.get(rank.max(1) - 1).copied()
+    }
+
+    // how many elves' totals fall into each bucket of `width` calories, given
+    // by where the bucket starts. the empty buckets in between are included
+    pub fn histogram(&self, width: u32) -> Vec<(u32, usize)> {
+        assert!(width > 0, "buckets have to be at least 1 calorie wide");
+        let Some(stats) = self.stats() else {
+            return Vec::new();
+        };
+        let (first, last) = (stats.min / width, stats.max / width);
+        let mut counts = vec![0; (last - first) as usize + 1];
+        for total in &self.totals {
+            counts[(total / width - first) as usize] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(bucket, count)| ((first + bucket as u32) * width, count))
+            .collect()
+    }
+}
+
+// the best `k` elves seen so far - so finding a few doesn't mean sorting
+// everyone, or even keeping them all around
+pub struct TopK {
+    k: usize,
+    // most calories first, and the elf that comes first in the input wins a tie.
+    // it's a min-heap, so the worst of the best is the one that goes
+    best: BinaryHeap<Reverse<(u32, Reverse<ElfId>)>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            best: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+
+    pub fn push(&mut self, id: ElfId, total: u32) {
+        self.best.push(Reverse((total, Reverse(id))));
+        if self.best.len() > self.k {
+            self.best.pop();
+        }
+    }
+
+    // best first
+    pub fn into_sorted(self) -> Vec<(ElfId, u32)> {
+        self.best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(id)))| (id, total))
+            .collect()
+    }
+}
+
+pub fn input_generator(input: &str) -> Result<Inventory, ParseError> {
+    let mut elves = Vec::new();
+    let mut current_elf = Vec::new();
+    for mut line in parse::lines(Day01::DAY, input) {
+        if line.is_empty() {
+            elves.push(Elf {
+                calories: current_elf,
+            });
+            current_elf = Vec::new();
+            continue;
+        }
+
+        current_elf.push(line.number()?);
+        line.end()?;
+    }
+    // the last elf doesn't have a blank line after it
+    if !current_elf.is_empty() {
+        elves.push(Elf {
+            calories: current_elf,
+        });
+    }
+
+    Ok(Inventory::new(elves))
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "couldn't read the calories: {e}"),
+            StreamError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+// the same as `input_generator(..).top_n(k)`, but adding each elf up as it's
+// read and only keeping the best `k`, so it doesn't matter how many elves
+// there are. lines are read one at a time into the same buffer, too
+pub fn stream_top_n(mut reader: impl BufRead, k: usize) -> Result<Vec<(ElfId, u32)>, StreamError> {
+    let mut top = TopK::new(k);
+    let mut elves = 0;
+    // the elf we're in the middle of, if we are
+    let mut current = None;
+    // blank lines after the one that ended the last elf - each of those is an elf
+    // with nothing, unless they're just trailing off at the end
+    let mut empty_elves = 0;
+    let mut buffer = String::new();
+    for number in 1.. {
+        buffer.clear();
+        if reader.read_line(&mut buffer).map_err(StreamError::Io)? == 0 {
+            break;
+        }
+        let mut line = Line::new(Day01::DAY, number, buffer.trim_end());
+        if line.is_empty() {
+            match current.take() {
+                Some(total) => {
+                    top.push(elves, total);
+                    elves += 1;
+                }
+                // (blank lines at the start don't count either)
+                None if elves > 0 => empty_elves += 1,
+                None => {}
+            }
+            continue;
+        }
+
+        for _ in 0..std::mem::take(&mut empty_elves) {
+            top.push(elves, 0);
+            elves += 1;
+        }
+        let calories: u32 = line.number().map_err(StreamError::Parse)?;
+        line.end().map_err(StreamError::Parse)?;
+        current = Some(current.unwrap_or(0) + calories);
+    }
+    if let Some(total) = current {
+        top.push(elves, total);
+    }
+
+    Ok(top.into_sorted())
+}
+
+pub fn solve_part1(input: &Inventory) -> u32 {
+    carried_by_top(input, 1)
+}
+
+pub fn solve_part2(input: &Inventory) -> u32 {
+    carried_by_top(input, 3)
+}
+
+// what the top `n` elves are carrying between them
+pub fn carried_by_top(input: &Inventory, n: usize) -> u32 {
+    input.top_n(n).iter().map(|&(_, total)| total).sum()
+}
+
+// a single snack, and whose it was
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Snack {
+    pub elf: ElfId,
+    // where it is in that elf's list
+    pub index: usize,
+    pub calories: u64,
+}
+
+// who carries what once the snacks have been shared out
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plan {
+    pub carriers: Vec<Vec<Snack>>,
+    pub totals: Vec<u64>,
+    // whether the heaviest load is definitely as light as it can be
+    pub exact: bool,
+}
+
+impl Plan {
+    pub fn heaviest(&self) -> u64 {
+        self.totals.iter().copied().max().unwrap_or(0)
+    }
+}
+
+// any more snacks than this and there's no point even starting to try every way
+// of sharing them out, so the greedy plan has to do
+const MAX_EXACT_SNACKS: usize = 20;
+// and even with fewer, some can take a long time to be sure of - this many steps
+// of the search and it settles for the best plan it's found so far
+const MAX_SEARCH_STEPS: usize = 2_000_000;
+
+// share every elf's snacks out between `carriers` carriers, so the heaviest
+// load is as light as possible
+pub fn balance(elves: &[Elf], carriers: usize) -> Plan {
+    assert!(carriers > 0, "there has to be someone to carry the snacks");
+    let mut snacks = elves
+        .iter()
+        .enumerate()
+        .flat_map(|(elf, e)| {
+            e.calories
+                .iter()
+                .enumerate()
+                .map(move |(index, &calories)| Snack {
+                    elf,
+                    index,
+                    calories: calories.into(),
+                })
+        })
+        .collect::<Vec<_>>();
+    // biggest first, which is what makes the greedy plan any good, and means the
+    // search below finds good plans (and so can rule out bad ones) early on
+    snacks.sort_by_key(|s| Reverse(s.calories));
+
+    // the heaviest load can't be less than an even share, or than the biggest snack
+    let total = snacks.iter().map(|s| s.calories).sum::<u64>();
+    let lower_bound = total
+        .div_ceil(carriers as u64)
+        .max(snacks.first().map_or(0, |s| s.calories));
+
+    let greedy = greedy_loads(&snacks, carriers);
+    let greedy_heaviest = heaviest(&snacks, &greedy, carriers);
+    let (loads, exact) = if snacks.len() <= MAX_EXACT_SNACKS {
+        let mut search = Search {
+            snacks: &snacks,
+            lower_bound,
+            best_heaviest: greedy_heaviest,
+            best: greedy,
+            loads: vec![0; carriers],
+            assignment: Vec::with_capacity(snacks.len()),
+            steps_left: MAX_SEARCH_STEPS,
+        };
+        search.assign(0);
+        (search.best, search.steps_left > 0)
+    } else {
+        // it might still have got lucky
+        (greedy, greedy_heaviest == lower_bound)
+    };
+
+    let mut plan = Plan {
+        carriers: vec![Vec::new(); carriers],
+        totals: vec![0; carriers],
+        exact,
+    };
+    for (snack, carrier) in snacks.into_iter().zip(loads) {
+        plan.totals[carrier] += snack.calories;
+        plan.carriers[carrier].push(snack);
+    }
+    plan
+}
+
+// each snack in turn goes to whoever's carrying the least (with the biggest
+// snacks first, that's never more than a third worse than the best)
+fn greedy_loads(snacks: &[Snack], carriers: usize) -> Vec<usize> {
+    let mut loads = BinaryHeap::new();
+    loads.extend((0..carriers).map(|carrier| Reverse((0, carrier))));
+    snacks
+        .iter()
+        .map(|snack| {
+            let Reverse((load, carrier)) = loads.pop().unwrap();
+            loads.push(Reverse((load + snack.calories, carrier)));
+            carrier
+        })
+        .collect()
+}
+
+fn heaviest(snacks: &[Snack], assignment: &[usize], carriers: usize) -> u64 {
+    let mut loads = vec![0; carriers];
+    for (snack, &carrier) in snacks.iter().zip(assignment) {
+        loads[carrier] += snack.calories;
+    }
+    loads.into_iter().max().unwrap_or(0)
+}
+
+// tries every way of sharing out the snacks, but gives up on any that's already
+// no better than the best so far
+struct Search<'a> {
+    snacks: &'a [Snack],
+    lower_bound: u64,
+    best: Vec<usize>,
+    best_heaviest: u64,
+    loads: Vec<u64>,
+    // who's carrying each of the snacks handed out so far
+    assignment: Vec<usize>,
+    // runs out if the search is taking too long
+    steps_left: usize,
+}
+
+impl Search<'_> {
+    fn assign(&mut self, next: usize) {
+        // can't do any better than this
+        if self.best_heaviest == self.lower_bound || self.steps_left == 0 {
+            return;
+        }
+        self.steps_left -= 1;
+        let Some(snack) = self.snacks.get(next) else {
+            let heaviest = self.loads.iter().copied().max().unwrap_or(0);
+            if heaviest < self.best_heaviest {
+                self.best_heaviest = heaviest;
+                self.best.clone_from(&self.assignment);
+            }
+            return;
+        };
+
+        for carrier in 0..self.loads.len() {
+            let load = self.loads[carrier] + snack.calories;
+            // two carriers with the same load are as good as each other, so only
+            // try the first
+            if load >= self.best_heaviest || self.loads[..carrier].contains(&self.loads[carrier]) {
+                continue;
+            }
+            self.loads[carrier] = load;
+            self.assignment.push(carrier);
+            self.assign(next + 1);
+            self.assignment.pop();
+            self.loads[carrier] -= snack.calories;
+        }
+    }
+}
+
+// `elves` elves, each carrying a few snacks
+pub fn generate(rng: &mut Rng, elves: usize) -> String {
+    let mut input = String::new();
+    for elf in 0..elves {
+        if elf > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.range(1..=15) {
+            input += &format!("{}\n", rng.range(1000..=70000));
+        }
+    }
+    input
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Part1Input = Inventory;
+    type Part2Input = Inventory;
+    type Part1Output = u32;
+    type Part2Output = u32;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day01);
+
+#[test]
+fn test_balance() {
+    let elves =
+        input_generator("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n")
+            .unwrap();
+
+    // 55000 in all, so 27500 each would be perfect - but everything's a
+    // multiple of 1000
+    let plan = balance(elves.elves(), 2);
+    assert!(plan.exact);
+    assert_eq!(plan.heaviest(), 28000);
+    assert_eq!(plan.totals.iter().sum::<u64>(), 55000);
+    for (carried, &total) in plan.carriers.iter().zip(&plan.totals) {
+        assert_eq!(carried.iter().map(|s| s.calories).sum::<u64>(), total);
+        for snack in carried {
+            assert_eq!(
+                u64::from(elves.elves()[snack.elf].calories[snack.index]),
+                snack.calories
+            );
+        }
+    }
+
+    // 55000 between three is 18334 each, so that's as good as it gets
+    let plan = balance(elves.elves(), 3);
+    assert_eq!(plan.heaviest(), 19000);
+
+    let big = input_generator(&generate(&mut Rng::new(2022), 20)).unwrap();
+    let plan = balance(big.elves(), 4);
+    assert!(!plan.exact);
+    let snacks = big.elves().iter().map(|e| e.calories.len()).sum::<usize>();
+    assert_eq!(plan.carriers.iter().map(Vec::len).sum::<usize>(), snacks);
+    let total = big.elves().iter().map(|e| e.total_calories()).sum::<u32>();
+    assert!(plan.heaviest() >= u64::from(total.div_ceil(4)));
+
+    // too many snacks to search, but one each is as good as it gets, and the
+    // greedy plan can tell
+    let many = (1..=30)
+        .map(|c| Elf { calories: vec![c] })
+        .collect::<Vec<_>>();
+    let plan = balance(&many, 30);
+    assert!(plan.exact);
+    assert_eq!(plan.heaviest(), 30);
+}
+
+#[cfg(test)]
+fn inventory(totals: &[u32]) -> Inventory {
+    Inventory::new(totals.iter().map(|&t| Elf { calories: vec![t] }).collect())
+}
+
+#[test]
+fn test_top_n_and_ranks() {
+    let elves = inventory(&[6000, 4000, 11000, 24000, 10000, 11000]);
+
+    assert_eq!(elves.top_n(3), [(3, 24000), (2, 11000), (5, 11000)]);
+    assert_eq!(elves.top_n(10).len(), 6);
+    assert_eq!(elves.top_n(0), []);
+
+    let ranks = (0..elves.len()).map(|id| elves.rank(id).unwrap());
+    assert_eq!(ranks.collect::<Vec<_>>(), [5, 6, 2, 1, 4, 3]);
+    assert_eq!(elves.rank(6), None);
+}
+
+#[test]
+fn test_stats() {
+    let elves = inventory(&[6000, 4000, 11000, 24000, 10000]);
+
+    let stats = elves.stats().unwrap();
+    assert_eq!((stats.elves, stats.min, stats.max), (5, 4000, 24000));
+    assert_eq!((stats.mean, stats.median), (11000.0, 10000.0));
+    assert_eq!(inventory(&[1, 4]).stats().unwrap().median, 2.5);
+    assert_eq!(inventory(&[]).stats(), None);
+
+    assert_eq!(elves.percentile(0.0), Some(4000));
+    assert_eq!(elves.percentile(50.0), Some(10000));
+    assert_eq!(elves.percentile(90.0), Some(24000));
+
+    assert_eq!(
+        elves.histogram(5000),
+        [(0, 1), (5000, 1), (10000, 2), (15000, 0), (20000, 1)]
+    );
+}
+
+#[test]
+fn test_streaming_matches() {
+    let mut rng = Rng::new(2022);
+    let mut inputs = vec![
+        generate(&mut rng, 300),
+        "\n1000\n\n\n\n2000\n3000\n\n\n".to_string(),
+    ];
+    inputs.extend(
+        aoc_common::examples::whitespace_variants(&inputs[0])
+            .into_iter()
+            .map(|(_, input)| input),
+    );
+
+    for input in inputs {
+        let inventory = input_generator(&input).unwrap();
+        for k in [0, 1, 3, 1000] {
+            let streamed = stream_top_n(input.as_bytes(), k).unwrap();
+            assert_eq!(streamed, inventory.top_n(k));
+        }
+        let top_three = stream_top_n(input.as_bytes(), 3).unwrap();
+        let total = top_three.iter().map(|&(_, total)| total).sum::<u32>();
+        assert_eq!(total, solve_part2(&inventory));
+    }
+}
+
+#[test]
+fn test_streaming_errors() {
+    let error = stream_top_n("1000\n\n20x0\n".as_bytes(), 3).unwrap_err();
+    let StreamError::Parse(error) = error else {
+        panic!("expected a parse error, got {error}");
+    };
+    assert_eq!((error.line, error.column), (3, 3));
+
+    let not_utf8 = [b'1', 0xff, b'\n'];
+    assert!(matches!(
+        stream_top_n(&not_utf8[..], 3),
+        Err(StreamError::Io(_))
+    ));
+}
+
+// the search for the best way to share the snacks out skips a lot - check it
+// against trying every single way
+#[cfg(test)]
+mod differential {
+    use super::*;
+    use aoc_common::differential::{self, Differential};
+
+    struct Balancing;
+
+    #[derive(Clone, Debug)]
+    struct Snacks {
+        calories: Vec<u32>,
+        carriers: usize,
+    }
+
+    impl Differential for Balancing {
+        type Input = Snacks;
+        type Output = u64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            Snacks {
+                calories: (0..rng.range(0..=8))
+                    .map(|_| rng.range(1..=20) as u32)
+                    .collect(),
+                carriers: rng.range(1..=4) as usize,
+            }
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            let mut smaller = differential::shrink_vec(&input.calories, 0)
+                .into_iter()
+                .map(|calories| Snacks {
+                    calories,
+                    ..input.clone()
+                })
+                .collect::<Vec<_>>();
+            if input.carriers > 1 {
+                smaller.push(Snacks {
+                    carriers: input.carriers - 1,
+                    ..input.clone()
+                });
+            }
+            smaller
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            let snacks = input.calories.len() as u32;
+            (0..input.carriers.pow(snacks))
+                .map(|mut way| {
+                    let mut loads = vec![0; input.carriers];
+                    for calories in &input.calories {
+                        loads[way % input.carriers] += u64::from(*calories);
+                        way /= input.carriers;
+                    }
+                    loads.into_iter().max().unwrap()
+                })
+                .min()
+                .unwrap()
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            // one elf each, so they don't all end up in the same place
+            let elves = input
+                .calories
+                .iter()
+                .map(|&c| Elf { calories: vec![c] })
+                .collect::<Vec<_>>();
+            balance(&elves, input.carriers).heaviest()
+        }
+    }
+
+    #[test]
+    fn test_balancing() {
+        differential::check::<Balancing>(300);
+    }
+}
@@ -0,0 +1,767 @@
+//! Day 2: Rock Paper Scissors.
+//!
+//! A strategy guide is a list of [`Game`]s. Part 1 reads the second column as the
+//! [`Play`] to make, and part 2 reads it as the [`Outcome`] we're after. Which
+//! moves there are, what beats what and how it's all scored come from the
+//! [`Rules`], so the same guides work for bigger games like
+//! Rock-Paper-Scissors-Lizard-Spock. Since nobody told us what the second column
+//! means, [`analyse`] tries every reading of it to see how much that matters.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use serde::Deserialize;
+use std::{collections::HashMap, sync::OnceLock};
+
+// a move, as an index into the rules' list of them
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Play(pub usize);
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Draw,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Move {
+    pub name: String,
+    // what it's written as in the first column, and in the second (for part 1)
+    pub opponent: String,
+    pub mine: String,
+    pub score: u32,
+}
+
+// which moves there are, what beats what, and what everything scores
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rules {
+    moves: Vec<Move>,
+    // beats[a][b] is whether move a beats move b
+    beats: Vec<Vec<bool>>,
+    // what part 2's second column says for each outcome, and what it scores
+    outcomes: HashMap<Outcome, (String, u32)>,
+    // what can be in each column, ready for parsing
+    opponent_column: Vec<(String, Play)>,
+    mine_column: Vec<(String, Play)>,
+    outcome_column: Vec<(String, Outcome)>,
+}
+
+// the game from the puzzle, and how its strategy guides are written
+pub const ROCK_PAPER_SCISSORS: &str = r#"
+moves = [
+    { name = "rock", opponent = "A", mine = "X", score = 1 },
+    { name = "paper", opponent = "B", mine = "Y", score = 2 },
+    { name = "scissors", opponent = "C", mine = "Z", score = 3 },
+]
+
+[outcomes]
+lose = { column = "X", score = 0 }
+draw = { column = "Y", score = 3 }
+win = { column = "Z", score = 6 }
+"#;
+
+// the rules as they're written down. each move can say which others it beats - or
+// if none of them do, they go round in a circle, with each move beating the half
+// of the others that come just before it (and wrapping round from the start)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Definition {
+    moves: Vec<MoveDefinition>,
+    outcomes: OutcomeDefinitions,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MoveDefinition {
+    name: String,
+    opponent: String,
+    mine: String,
+    score: u32,
+    beats: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeDefinitions {
+    lose: OutcomeDefinition,
+    draw: OutcomeDefinition,
+    win: OutcomeDefinition,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeDefinition {
+    column: String,
+    score: u32,
+}
+
+// nothing can be written the same as something else in the same column
+fn check_unique<'a>(what: &str, symbols: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    let mut seen = Vec::new();
+    for symbol in symbols {
+        if symbol.is_empty() || symbol.contains(char::is_whitespace) {
+            return Err(format!(
+                "{what} {symbol:?} has to be something without spaces"
+            ));
+        }
+        if seen.contains(&symbol) {
+            return Err(format!("there's more than one {what} `{symbol}`"));
+        }
+        seen.push(symbol);
+    }
+    Ok(())
+}
+
+// the tags for one column, longest first so one that starts with another isn't
+// mistaken for it
+fn column<T>(symbols: impl Iterator<Item = (String, T)>) -> Vec<(String, T)> {
+    let mut options = symbols.collect::<Vec<_>>();
+    options.sort_by_key(|(symbol, _)| std::cmp::Reverse(symbol.len()));
+    options
+}
+
+impl Rules {
+    // these only need reading the once
+    pub fn rock_paper_scissors() -> &'static Self {
+        static RULES: OnceLock<Rules> = OnceLock::new();
+        RULES.get_or_init(|| Self::parse(ROCK_PAPER_SCISSORS).expect("the built-in rules are fine"))
+    }
+
+    // rules written in TOML, like `ROCK_PAPER_SCISSORS`
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let definition: Definition = toml::from_str(text).map_err(|e| e.to_string())?;
+        let count = definition.moves.len();
+        check_unique("move", definition.moves.iter().map(|m| m.name.as_str()))?;
+        check_unique(
+            "opponent move",
+            definition.moves.iter().map(|m| m.opponent.as_str()),
+        )?;
+        check_unique("my move", definition.moves.iter().map(|m| m.mine.as_str()))?;
+        let outcomes = &definition.outcomes;
+        check_unique(
+            "outcome",
+            [&outcomes.lose, &outcomes.draw, &outcomes.win]
+                .into_iter()
+                .map(|o| o.column.as_str()),
+        )?;
+
+        let mut beats = vec![vec![false; count]; count];
+        let explicit = definition
+            .moves
+            .iter()
+            .filter(|m| m.beats.is_some())
+            .count();
+        if explicit == 0 {
+            if count % 2 != 1 {
+                return Err(format!(
+                    "{count} moves can't go round in a circle - each one would draw with the one opposite it"
+                ));
+            }
+            for (a, row) in beats.iter_mut().enumerate() {
+                for step in 1..=count / 2 {
+                    row[(a + count - step) % count] = true;
+                }
+            }
+        } else if explicit < count {
+            return Err("either every move says what it beats, or none of them do".to_owned());
+        } else {
+            for (a, m) in definition.moves.iter().enumerate() {
+                for beaten in m.beats.iter().flatten() {
+                    let b = definition
+                        .moves
+                        .iter()
+                        .position(|other| &other.name == beaten)
+                        .ok_or_else(|| {
+                            format!("`{}` beats `{beaten}`, which isn't a move", m.name)
+                        })?;
+                    beats[a][b] = true;
+                }
+            }
+        }
+
+        let names = definition.moves.iter().map(|m| &m.name).enumerate();
+        for (a, name) in names.clone() {
+            if beats[a][a] {
+                return Err(format!("`{name}` can't beat itself"));
+            }
+            for (b, other) in names.clone().skip(a + 1) {
+                match (beats[a][b], beats[b][a]) {
+                    (true, true) => {
+                        return Err(format!("`{name}` and `{other}` both beat each other"))
+                    }
+                    (false, false) => {
+                        return Err(format!("nothing says whether `{name}` or `{other}` wins"))
+                    }
+                    _ => {}
+                }
+            }
+            // otherwise there'd be no way to get that outcome in part 2
+            if !beats[a].contains(&true) || !(0..count).any(|b| beats[b][a]) {
+                return Err(format!(
+                    "`{name}` has to beat something and lose to something"
+                ));
+            }
+        }
+
+        let outcome = |o: &OutcomeDefinition| (o.column.clone(), o.score);
+        let plays = |symbol: fn(&MoveDefinition) -> &String| {
+            column(
+                definition
+                    .moves
+                    .iter()
+                    .enumerate()
+                    .map(|(index, m)| (symbol(m).clone(), Play(index))),
+            )
+        };
+        let opponent_column = plays(|m| &m.opponent);
+        let mine_column = plays(|m| &m.mine);
+        let outcome_column = column(
+            [
+                (&outcomes.lose, Outcome::Lose),
+                (&outcomes.draw, Outcome::Draw),
+                (&outcomes.win, Outcome::Win),
+            ]
+            .into_iter()
+            .map(|(o, outcome)| (o.column.clone(), outcome)),
+        );
+        Ok(Self {
+            moves: definition
+                .moves
+                .into_iter()
+                .map(|m| Move {
+                    name: m.name,
+                    opponent: m.opponent,
+                    mine: m.mine,
+                    score: m.score,
+                })
+                .collect(),
+            beats,
+            outcomes: HashMap::from([
+                (Outcome::Lose, outcome(&outcomes.lose)),
+                (Outcome::Draw, outcome(&outcomes.draw)),
+                (Outcome::Win, outcome(&outcomes.win)),
+            ]),
+            opponent_column,
+            mine_column,
+            outcome_column,
+        })
+    }
+
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    pub fn beats(&self, a: Play, b: Play) -> bool {
+        self.beats[a.0][b.0]
+    }
+
+    pub fn score(&self, play: Play) -> u32 {
+        self.moves[play.0].score
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> u32 {
+        self.outcomes[&outcome].1
+    }
+
+    // all the plays that end up this way against the opponent's
+    pub fn plays_for(&self, opponent: Play, outcome: Outcome) -> impl Iterator<Item = Play> + '_ {
+        (0..self.moves.len())
+            .map(Play)
+            .filter(move |&me| Game { me, opponent }.outcome(self) == outcome)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Game {
+    pub me: Play,
+    pub opponent: Play,
+}
+
+impl Game {
+    pub fn outcome(&self, rules: &Rules) -> Outcome {
+        if rules.beats(self.me, self.opponent) {
+            Outcome::Win
+        } else if rules.beats(self.opponent, self.me) {
+            Outcome::Lose
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    pub fn score(&self, rules: &Rules) -> u32 {
+        rules.score(self.me) + rules.outcome_score(self.outcome(rules))
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Strategy {
+    pub opponent: Play,
+    pub desired_outcome: Outcome,
+}
+
+impl Strategy {
+    // with more than three moves there can be a few ways to get the outcome, so
+    // this picks the one that scores the most (or the first of those)
+    pub fn choose_play(&self, rules: &Rules) -> Play {
+        rules
+            .plays_for(self.opponent, self.desired_outcome)
+            .max_by_key(|&play| (rules.score(play), std::cmp::Reverse(play.0)))
+            .expect("the rules always have a way to win, lose or draw")
+    }
+}
+
+// the rounds of a strategy guide, along with the rules it was read with
+#[derive(Clone, Debug)]
+pub struct Guide<'a, T> {
+    pub rules: &'a Rules,
+    pub rounds: Vec<T>,
+}
+
+// every line is the opponent's play, a space, then the second column
+fn parse_line<T: Copy>(
+    mut line: Line,
+    rules: &Rules,
+    second: &[(String, T)],
+) -> Result<(Play, T), ParseError> {
+    let opp = line.one_of(&rules.opponent_column)?;
+    line.tag(" ")?;
+    let second = line.one_of(second)?;
+    line.end()?;
+    Ok((opp, second))
+}
+
+// part 1's way of reading a guide, with the second column as my move
+pub fn parse_games<'a>(rules: &'a Rules, input: &str) -> Result<Guide<'a, Game>, ParseError> {
+    let rounds = parse::lines(Day02::DAY, input)
+        .map(|l| {
+            let (opp, m) = parse_line(l, rules, &rules.mine_column)?;
+            Ok(Game {
+                opponent: opp,
+                me: m,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Guide { rules, rounds })
+}
+
+// and part 2's, with it as the outcome
+pub fn parse_strategies<'a>(
+    rules: &'a Rules,
+    input: &str,
+) -> Result<Guide<'a, Strategy>, ParseError> {
+    let rounds = parse::lines(Day02::DAY, input)
+        .map(|l| {
+            let (opp, o) = parse_line(l, rules, &rules.outcome_column)?;
+            Ok(Strategy {
+                opponent: opp,
+                desired_outcome: o,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(Guide { rules, rounds })
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Guide<'static, Game>, ParseError> {
+    parse_games(Rules::rock_paper_scissors(), input)
+}
+
+pub fn input_generator_part2(input: &str) -> Result<Guide<'static, Strategy>, ParseError> {
+    parse_strategies(Rules::rock_paper_scissors(), input)
+}
+
+pub fn solve_part1(input: &Guide<Game>) -> u32 {
+    input.rounds.iter().map(|g| g.score(input.rules)).sum()
+}
+
+pub fn solve_part2(input: &Guide<Strategy>) -> u32 {
+    input
+        .rounds
+        .iter()
+        .map(|s| {
+            Game {
+                opponent: s.opponent,
+                me: s.choose_play(input.rules),
+            }
+            .score(input.rules)
+        })
+        .sum()
+}
+
+// one way of reading the second column - what each thing in it means - and what
+// the guide scores if it's read that way
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Interpretation<T> {
+    pub meanings: Vec<(String, T)>,
+    pub total: u32,
+}
+
+// every way of reading the second column as one thing, best first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Readings<T> {
+    pub all: Vec<Interpretation<T>>,
+}
+
+impl<T> Readings<T> {
+    pub fn best(&self) -> Option<&Interpretation<T>> {
+        self.all.first()
+    }
+
+    pub fn worst(&self) -> Option<&Interpretation<T>> {
+        self.all.last()
+    }
+
+    // how much the answer depends on getting the reading right
+    pub fn spread(&self) -> u32 {
+        match (self.best(), self.worst()) {
+            (Some(best), Some(worst)) => best.total - worst.total,
+            _ => 0,
+        }
+    }
+}
+
+// the guide read with the second column as moves (like part 1) and as outcomes
+// (like part 2), every way round
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Analysis {
+    pub as_plays: Readings<Play>,
+    pub as_outcomes: Readings<Outcome>,
+}
+
+// every way of picking `k` different things out of `n`, in order
+fn arrangements(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut all = Vec::new();
+    for shorter in arrangements(n, k - 1) {
+        for next in (0..n).filter(|i| !shorter.contains(i)) {
+            let mut longer = shorter.clone();
+            longer.push(next);
+            all.push(longer);
+        }
+    }
+    all
+}
+
+// every way the second column could be matched up with moves, or with outcomes
+// (each thing in it meaning something different), and what the guide scores
+// for each. with more things in the column than moves or outcomes, there's no
+// way of reading it as those
+pub fn analyse(rules: &Rules, input: &str) -> Result<Analysis, ParseError> {
+    // only how many of each round there are matters
+    let mut rounds = HashMap::<(Play, &str), u32>::new();
+    for mut line in parse::lines(Day02::DAY, input) {
+        let opponent = line.one_of(&rules.opponent_column)?;
+        line.tag(" ")?;
+        let second = line.take_while1("a move or an outcome", |c| !c.is_whitespace())?;
+        line.end()?;
+        *rounds.entry((opponent, second)).or_default() += 1;
+    }
+    let mut symbols = rounds.keys().map(|&(_, s)| s).collect::<Vec<_>>();
+    symbols.sort_unstable();
+    symbols.dedup();
+
+    fn readings<T: Copy>(
+        symbols: &[&str],
+        options: &[T],
+        rounds: &HashMap<(Play, &str), u32>,
+        score: impl Fn(Play, T) -> u32,
+    ) -> Readings<T> {
+        let mut all = arrangements(options.len(), symbols.len())
+            .into_iter()
+            .map(|arrangement| {
+                let meanings = symbols
+                    .iter()
+                    .zip(arrangement)
+                    .map(|(&symbol, i)| (symbol, options[i]))
+                    .collect::<HashMap<_, _>>();
+                let total = rounds
+                    .iter()
+                    .map(|(&(opponent, symbol), &count)| count * score(opponent, meanings[symbol]))
+                    .sum();
+                Interpretation {
+                    meanings: symbols
+                        .iter()
+                        .map(|&symbol| (symbol.to_owned(), meanings[symbol]))
+                        .collect(),
+                    total,
+                }
+            })
+            .collect::<Vec<_>>();
+        all.sort_by_key(|i| std::cmp::Reverse(i.total));
+        Readings { all }
+    }
+
+    let plays = (0..rules.moves.len()).map(Play).collect::<Vec<_>>();
+    let as_plays = readings(&symbols, &plays, &rounds, |opponent, me| {
+        Game { me, opponent }.score(rules)
+    });
+    let outcomes = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+    let as_outcomes = readings(&symbols, &outcomes, &rounds, |opponent, desired_outcome| {
+        let me = Strategy {
+            opponent,
+            desired_outcome,
+        }
+        .choose_play(rules);
+        Game { me, opponent }.score(rules)
+    });
+
+    Ok(Analysis {
+        as_plays,
+        as_outcomes,
+    })
+}
+
+// a strategy guide with `rounds` rounds in it
+pub fn generate(rng: &mut Rng, rounds: usize) -> String {
+    (0..rounds)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['A', 'B', 'C']),
+                rng.choose(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    // the puzzle's rules are only read the once, and kept for good
+    type Part1Input = Guide<'static, Game>;
+    type Part2Input = Guide<'static, Strategy>;
+    type Part1Output = u32;
+    type Part2Output = u32;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part2(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day02);
+
+#[test]
+fn test_analyse() {
+    let rules = Rules::rock_paper_scissors();
+    let guide = "A Y\nB X\nC Z\n";
+    let analysis = analyse(rules, guide).unwrap();
+    let [rock, paper, scissors] = [0, 1, 2].map(Play);
+
+    // every way of matching X, Y and Z up with moves, and with outcomes
+    assert_eq!(analysis.as_plays.all.len(), 6);
+    assert_eq!(analysis.as_outcomes.all.len(), 6);
+
+    // the puzzle's readings are in there, with the puzzle's answers
+    let reading = |symbols: &[(&str, Play)]| {
+        let meanings = symbols
+            .iter()
+            .map(|&(s, p)| (s.to_owned(), p))
+            .collect::<Vec<_>>();
+        let all = &analysis.as_plays.all;
+        all.iter().find(|i| i.meanings == meanings).unwrap().total
+    };
+    assert_eq!(reading(&[("X", rock), ("Y", paper), ("Z", scissors)]), 15);
+    let puzzle = analysis
+        .as_outcomes
+        .all
+        .iter()
+        .find(|i| i.meanings[0].1 == Outcome::Lose && i.meanings[2].1 == Outcome::Win)
+        .unwrap();
+    assert_eq!(puzzle.total, 12);
+
+    // winning every round with the highest scoring move is as good as it gets:
+    // paper against rock (8), scissors against paper (9), rock against scissors (7)
+    let best = analysis.as_plays.best().unwrap();
+    assert_eq!(best.total, 24);
+    assert_eq!(
+        best.meanings,
+        [
+            ("X".to_owned(), scissors),
+            ("Y".to_owned(), paper),
+            ("Z".to_owned(), rock)
+        ]
+    );
+    // and losing them all, the other way round
+    assert_eq!(analysis.as_plays.worst().unwrap().total, 6);
+    assert_eq!(analysis.as_plays.spread(), 18);
+    // as outcomes, X, Y and Z have to mean different things too, so only one
+    // round can be a win. the best is losing to rock with scissors (3), beating paper with
+    // scissors (9), and drawing with scissors (6) - and part 2's reading is the worst
+    assert_eq!(analysis.as_outcomes.best().unwrap().total, 18);
+    assert_eq!(analysis.as_outcomes.worst(), Some(puzzle));
+
+    // four different things can't be three outcomes
+    let analysis = analyse(rules, "A X\nA Y\nA Z\nA W\n").unwrap();
+    assert_eq!(analysis.as_outcomes.all, []);
+    assert_eq!(analysis.as_outcomes.spread(), 0);
+}
+
+#[cfg(test)]
+const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = r#"
+moves = [
+    { name = "rock", opponent = "A", mine = "V", score = 1 },
+    { name = "spock", opponent = "B", mine = "W", score = 2 },
+    { name = "paper", opponent = "C", mine = "X", score = 3 },
+    { name = "lizard", opponent = "D", mine = "Y", score = 4 },
+    { name = "scissors", opponent = "E", mine = "Z", score = 5 },
+]
+
+[outcomes]
+lose = { column = "L", score = 0 }
+draw = { column = "D", score = 3 }
+win = { column = "W", score = 6 }
+"#;
+
+#[test]
+fn test_rock_paper_scissors() {
+    let rules = Rules::rock_paper_scissors();
+    let [rock, paper, scissors] = [0, 1, 2].map(Play);
+    assert!(
+        rules.beats(paper, rock) && rules.beats(scissors, paper) && rules.beats(rock, scissors)
+    );
+    assert!(!rules.beats(rock, paper) && !rules.beats(rock, rock));
+
+    let choose = |opponent, desired_outcome| {
+        Strategy {
+            opponent,
+            desired_outcome,
+        }
+        .choose_play(rules)
+    };
+    assert_eq!(choose(rock, Outcome::Win), paper);
+    assert_eq!(choose(rock, Outcome::Lose), scissors);
+    assert_eq!(choose(scissors, Outcome::Draw), scissors);
+}
+
+#[test]
+fn test_rock_paper_scissors_lizard_spock() {
+    let rules = Rules::parse(ROCK_PAPER_SCISSORS_LIZARD_SPOCK).unwrap();
+    let [rock, spock, paper, lizard, scissors] = [0, 1, 2, 3, 4].map(Play);
+    for (winner, loser) in [
+        (scissors, paper),
+        (paper, rock),
+        (rock, lizard),
+        (lizard, spock),
+        (spock, scissors),
+        (scissors, lizard),
+        (lizard, paper),
+        (paper, spock),
+        (spock, rock),
+        (rock, scissors),
+    ] {
+        assert!(rules.beats(winner, loser) && !rules.beats(loser, winner));
+    }
+
+    // the same thing, spelled out
+    let explicit = ROCK_PAPER_SCISSORS_LIZARD_SPOCK
+        .replacen(
+            "score = 1 }",
+            r#"score = 1, beats = ["lizard", "scissors"] }"#,
+            1,
+        )
+        .replacen(
+            "score = 2 }",
+            r#"score = 2, beats = ["scissors", "rock"] }"#,
+            1,
+        )
+        .replacen(
+            "score = 3 }",
+            r#"score = 3, beats = ["rock", "spock"] }"#,
+            1,
+        )
+        .replacen(
+            "score = 4 }",
+            r#"score = 4, beats = ["spock", "paper"] }"#,
+            1,
+        )
+        .replacen(
+            "score = 5 }",
+            r#"score = 5, beats = ["paper", "lizard"] }"#,
+            1,
+        );
+    assert_eq!(Rules::parse(&explicit).unwrap(), rules);
+
+    // a draw (3 + 3), then beating spock with lizard (4 + 6) - the better of the
+    // two ways to win
+    let guide = "C X\nB Y\n";
+    assert_eq!(solve_part1(&parse_games(&rules, guide).unwrap()), 16);
+    let guide = "C D\nB W\n";
+    assert_eq!(solve_part2(&parse_strategies(&rules, guide).unwrap()), 16);
+
+    let error = parse_games(&rules, "F X\n").unwrap_err();
+    assert_eq!(error.expected, "`A`, `B`, `C`, `D` or `E`");
+}
+
+#[test]
+fn test_bad_rules() {
+    let error = |from: &str, to: &str| {
+        let changed = ROCK_PAPER_SCISSORS.replace(from, to);
+        assert_ne!(changed, ROCK_PAPER_SCISSORS, "{from:?} isn't in the rules");
+        Rules::parse(&changed).unwrap_err()
+    };
+
+    assert!(error(
+        r#"    { name = "scissors", opponent = "C", mine = "Z", score = 3 },
+"#,
+        ""
+    )
+    .contains("2 moves can't go round in a circle"));
+    assert!(
+        error(r#"opponent = "C""#, r#"opponent = "A""#).contains("more than one opponent move `A`")
+    );
+    assert!(error(r#"mine = "Z""#, r#"mine = "X""#).contains("more than one my move `X`"));
+    assert!(error(r#"column = "Z""#, r#"column = "X""#).contains("more than one outcome `X`"));
+    assert!(
+        error("score = 1 }", r#"score = 1, beats = ["scissors"] }"#).contains("either every move")
+    );
+    assert!(error("score = 1", "points = 1").contains("unknown field `points`"));
+
+    let explicit = |rock: &str, paper: &str, scissors: &str| {
+        ROCK_PAPER_SCISSORS
+            .replacen("score = 1 }", &format!("score = 1, beats = [{rock}] }}"), 1)
+            .replacen(
+                "score = 2 }",
+                &format!("score = 2, beats = [{paper}] }}"),
+                1,
+            )
+            .replacen(
+                "score = 3 }",
+                &format!("score = 3, beats = [{scissors}] }}"),
+                1,
+            )
+    };
+    assert!(Rules::parse(&explicit(r#""scissors""#, r#""rock""#, r#""paper""#)).is_ok());
+    let error = |rock, paper, scissors| Rules::parse(&explicit(rock, paper, scissors)).unwrap_err();
+    assert!(error(r#""rock""#, r#""rock""#, r#""paper""#).contains("`rock` can't beat itself"));
+    assert!(error(r#""paper""#, r#""rock""#, r#""paper""#)
+        .contains("`rock` and `paper` both beat each other"));
+    assert!(error(r#""scissors""#, "", r#""paper""#)
+        .contains("nothing says whether `rock` or `paper` wins"));
+    assert!(error(r#""stone""#, r#""rock""#, r#""paper""#)
+        .contains("`rock` beats `stone`, which isn't a move"));
+    assert!(error(r#""scissors""#, r#""rock", "scissors""#, "")
+        .contains("`paper` has to beat something and lose to something"));
+}
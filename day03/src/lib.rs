@@ -0,0 +1,234 @@
+//! Day 3: Rucksack Reorganization.
+//!
+//! Each [`Rucksack`] has two [`Compartment`]s. Part 1 finds the item in both halves
+//! of each rucksack, and part 2 the badge shared by each [`ElfGroup`] of three.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use std::collections::HashSet;
+
+pub type Item = char;
+
+pub fn priority(item: Item) -> u32 {
+    let ascii = item as u32;
+    if ascii >= 97 {
+        ascii - 96
+    } else {
+        ascii - 64 + 26
+    }
+}
+
+pub struct Compartment {
+    items: HashSet<Item>,
+}
+
+pub struct Rucksack {
+    first_compartment: Compartment,
+    second_compartment: Compartment,
+}
+
+impl Rucksack {
+    fn find_common_item(&self) -> Option<Item> {
+        self.first_compartment
+            .items
+            .intersection(&self.second_compartment.items)
+            .next()
+            .copied()
+    }
+
+    // part 1's parser makes sure there is one
+    pub fn common_item(&self) -> Item {
+        self.find_common_item()
+            .expect("there's an item in both compartments")
+    }
+
+    pub fn items(&self) -> HashSet<Item> {
+        self.first_compartment
+            .items
+            .union(&self.second_compartment.items)
+            .cloned()
+            .collect::<HashSet<_>>()
+    }
+
+    pub fn new(input: &str) -> Self {
+        let length = input.len();
+        let (first, second) = input.split_at(length / 2);
+        Self {
+            first_compartment: Compartment {
+                items: first.chars().map(|c| c as Item).collect::<HashSet<_>>(),
+            },
+            second_compartment: Compartment {
+                items: second.chars().map(|c| c as Item).collect::<HashSet<_>>(),
+            },
+        }
+    }
+}
+
+pub struct ElfGroup {
+    first: Rucksack,
+    second: Rucksack,
+    third: Rucksack,
+}
+
+impl ElfGroup {
+    fn find_common_item(&self) -> Option<Item> {
+        let first_intersection = self
+            .first
+            .items()
+            .intersection(&self.second.items())
+            .cloned()
+            .collect::<HashSet<_>>();
+        let third = &self.third.items();
+        let mut intersection = first_intersection.intersection(third);
+        intersection.next().copied()
+    }
+
+    // part 2's parser makes sure there is one
+    pub fn common_item(&self) -> Item {
+        self.find_common_item()
+            .expect("all three elves carry the badge")
+    }
+}
+
+fn parse_rucksack(mut line: Line) -> Result<Rucksack, ParseError> {
+    let items = line.take_while(|c| c.is_ascii_alphabetic());
+    if !line.is_empty() {
+        return Err(line.error("an item (a-z or A-Z)"));
+    }
+    // the two compartments have to be the same size
+    if !items.len().is_multiple_of(2) {
+        return Err(line.error("an even number of items"));
+    }
+    Ok(Rucksack::new(items))
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    parse::lines(Day03::DAY, input)
+        .map(|line| {
+            let rucksack = parse_rucksack(line.clone())?;
+            if rucksack.find_common_item().is_none() {
+                return Err(line.error("an item in both halves of the rucksack"));
+            }
+            Ok(rucksack)
+        })
+        .collect()
+}
+
+pub fn input_generator_part2(input: &str) -> Result<Vec<ElfGroup>, ParseError> {
+    let mut groups = Vec::new();
+    let mut lines = parse::lines(Day03::DAY, input);
+    while let Some(f) = lines.next() {
+        let expected = "another rucksack (elves come in groups of three)";
+        let s = lines.expect(expected)?;
+        let t = lines.expect(expected)?;
+        let group = ElfGroup {
+            first: parse_rucksack(f)?,
+            second: parse_rucksack(s)?,
+            third: parse_rucksack(t.clone())?,
+        };
+        if group.find_common_item().is_none() {
+            return Err(t.error("a rucksack with an item the other two elves have as well"));
+        }
+        groups.push(group);
+    }
+
+    Ok(groups)
+}
+
+pub fn solve_part1(input: &[Rucksack]) -> u32 {
+    input.iter().map(|r| priority(r.common_item())).sum()
+}
+
+pub fn solve_part2(input: &[ElfGroup]) -> u32 {
+    input.iter().map(|g| priority(g.common_item())).sum()
+}
+
+// `rucksacks` rucksacks, rounded up to whole groups of three. each elf in a group
+// packs from their own set of item types, apart from the group's badge - so the
+// badge is the only type all three share, and the one item type in both of a
+// rucksack's compartments is the one from the elf's set that's put in both
+pub fn generate(rng: &mut Rng, rucksacks: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..rucksacks.div_ceil(3).max(1) {
+        let mut types = ('a'..='z').chain('A'..='Z').collect::<Vec<_>>();
+        rng.shuffle(&mut types);
+        let badge = types[0];
+        for own in types[1..].chunks(17) {
+            let shared = own[0];
+            let (left_only, right_only) = own[1..].split_at(8);
+            let half = rng.range(2..=16) as usize;
+            let mut left = vec![shared];
+            let mut right = vec![shared];
+            if rng.one_in(2) {
+                left.push(badge);
+            } else {
+                right.push(badge);
+            }
+            while left.len() < half {
+                left.push(*rng.choose(left_only));
+            }
+            while right.len() < half {
+                right.push(*rng.choose(right_only));
+            }
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+            input.extend(left);
+            input.extend(right);
+            input.push('\n');
+        }
+    }
+    input
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Part1Input = Vec<Rucksack>;
+    type Part2Input = Vec<ElfGroup>;
+    type Part1Output = u32;
+    type Part2Output = u32;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part2(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day03);
+
+#[test]
+fn test_day03_nothing_in_common() {
+    let error = input_generator_part1("vJrwpWtwJgWrhcsFMMfFFhFp\nabcdef\n")
+        .err()
+        .unwrap();
+    assert_eq!((error.day, error.line, error.column), (3, 2, 1));
+    assert_eq!(error.expected, "an item in both halves of the rucksack");
+
+    let error = input_generator_part2("abAB\nacAC\nxyXY\n").err().unwrap();
+    assert_eq!((error.line, error.column), (3, 1));
+    assert_eq!(
+        error.expected,
+        "a rucksack with an item the other two elves have as well"
+    );
+}
@@ -0,0 +1,110 @@
+//! Day 4: Camp Cleanup.
+//!
+//! Each [`AssignmentPair`] is two [`SectionRange`]s - count the pairs where one
+//! range contains the other, then the pairs that overlap at all.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+
+pub struct SectionRange {
+    start_id: u32,
+    end_id: u32,
+}
+
+impl SectionRange {
+    pub fn fully_contains(&self, other: &SectionRange) -> bool {
+        self.start_id <= other.start_id && self.end_id >= other.end_id
+    }
+
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        // only way they can't overlap is if one starts after the other ends
+        !(self.end_id < other.start_id || other.end_id < self.start_id)
+    }
+}
+
+pub struct AssignmentPair {
+    first: SectionRange,
+    second: SectionRange,
+}
+
+fn parse_range(line: &mut Line) -> Result<SectionRange, ParseError> {
+    let start_id = line.number()?;
+    line.tag("-")?;
+    let end_id = line.number()?;
+    Ok(SectionRange { start_id, end_id })
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<AssignmentPair>, ParseError> {
+    let mut pairs = Vec::new();
+    for mut l in parse::lines(Day04::DAY, input) {
+        let first = parse_range(&mut l)?;
+        l.tag(",")?;
+        let second = parse_range(&mut l)?;
+        l.end()?;
+        pairs.push(AssignmentPair { first, second });
+    }
+
+    Ok(pairs)
+}
+
+pub fn solve_part1(input: &[AssignmentPair]) -> usize {
+    input
+        .iter()
+        .filter(|p| p.first.fully_contains(&p.second) || p.second.fully_contains(&p.first))
+        .count()
+}
+
+pub fn solve_part2(input: &[AssignmentPair]) -> usize {
+    input.iter().filter(|p| p.first.overlaps(&p.second)).count()
+}
+
+// `pairs` pairs of section assignments
+pub fn generate(rng: &mut Rng, pairs: usize) -> String {
+    let mut assignment = || {
+        let start = rng.range(1..=99);
+        (start, rng.range(start..=99))
+    };
+    (0..pairs)
+        .map(|_| {
+            let ((a, b), (c, d)) = (assignment(), assignment());
+            format!("{a}-{b},{c}-{d}\n")
+        })
+        .collect()
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Part1Input = Vec<AssignmentPair>;
+    type Part2Input = Vec<AssignmentPair>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day04);
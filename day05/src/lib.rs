@@ -0,0 +1,346 @@
+//! Day 5: Supply Stacks.
+//!
+//! The [`Input`] is the starting stacks of crates plus the [`Instruction`]s for
+//! moving them. The crane moves crates one at a time in part 1, and several at
+//! once in part 2.
+
+use aoc_common::{parse, rng::Rng, NoParams, ParseError, Simulation, Solution};
+use std::collections::HashMap;
+
+pub type Stack = Vec<char>;
+pub type Dock = HashMap<u32, Stack>;
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Instruction {
+    start_stack: u32,
+    end_stack: u32,
+    number_to_move: usize,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct Input {
+    starting_layout: Dock,
+    instructions: Vec<Instruction>,
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Input, ParseError> {
+    // start the dock off with something - if we find more columns we'll add them as we go
+    let mut dock: Dock = (1..2).map(|i| (i, Vec::new())).collect();
+    let mut instructions = Vec::new();
+    for mut l in parse::lines(Day05::DAY, input) {
+        let text = l.text();
+        if text.contains('[') {
+            // the line specifies some crates - add them to the dock
+            let crates = text.match_indices('[');
+            for (idx, _) in crates {
+                let stack_number = (idx / 4 + 1) as u32;
+                let _crate = match text[idx + 1..].chars().next() {
+                    Some(c) if c.is_ascii_uppercase() => c,
+                    _ => return Err(l.error_at(idx + 1, "a crate (A-Z)")),
+                };
+                if !text[idx + 2..].starts_with(']') {
+                    return Err(l.error_at(idx + 2, "`]`"));
+                }
+                dock.entry(stack_number).or_default().insert(0, _crate);
+            }
+        } else if l.try_tag("move ") {
+            let number_to_move = l.number()?;
+            l.tag(" from ")?;
+            let stack_position = l.position();
+            let starting_stack = l.number()?;
+            if !dock.contains_key(&starting_stack) {
+                return Err(l.error_at(stack_position, "the number of a stack"));
+            }
+            l.tag(" to ")?;
+            let stack_position = l.position();
+            let ending_stack = l.number()?;
+            if !dock.contains_key(&ending_stack) {
+                return Err(l.error_at(stack_position, "the number of a stack"));
+            }
+            l.end()?;
+            instructions.push(Instruction {
+                number_to_move,
+                start_stack: starting_stack,
+                end_stack: ending_stack,
+            })
+        } else {
+            // the only other things are the stack numbers, and blank lines
+            l.take_while(|c| c == ' ' || c.is_ascii_digit());
+            if !l.is_empty() {
+                return Err(l.error("a row of crates, stack numbers or a `move`"));
+            }
+        }
+    }
+
+    Ok(Input {
+        starting_layout: dock,
+        instructions,
+    })
+}
+
+// the crane in part 1 moves crates one at a time; the one in part 2 can pick
+// up a whole pile at once and keep it in the same order
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Model {
+    CrateMover9000,
+    CrateMover9001,
+}
+
+// carries out the rearrangement one instruction at a time
+pub struct Crane<'a> {
+    model: Model,
+    dock: Dock,
+    instructions: &'a [Instruction],
+    done: usize,
+}
+
+impl<'a> Crane<'a> {
+    pub fn new(input: &'a Input, model: Model) -> Self {
+        Self {
+            model,
+            dock: input.starting_layout.clone(),
+            instructions: &input.instructions,
+            done: 0,
+        }
+    }
+
+    pub fn dock(&self) -> &Dock {
+        &self.dock
+    }
+
+    // the crate on top of each stack, in order
+    pub fn top_crates(&self) -> String {
+        let number_of_stacks = self.dock.keys().count() as u32;
+        (1..=number_of_stacks)
+            .filter_map(|i| self.dock[&i].last())
+            .collect()
+    }
+}
+
+impl Simulation for Crane<'_> {
+    fn step(&mut self) -> bool {
+        let Some(i) = self.instructions.get(self.done) else {
+            return false;
+        };
+
+        let start_stack = self.dock.get_mut(&i.start_stack).unwrap();
+        let mut picked_up = start_stack.split_off(start_stack.len() - i.number_to_move);
+        if self.model == Model::CrateMover9000 {
+            // one at a time, so the last one picked up goes down first
+            picked_up.reverse();
+        }
+        self.dock.get_mut(&i.end_stack).unwrap().extend(picked_up);
+
+        self.done += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.done
+    }
+
+    // the stacks the way the puzzle draws them
+    fn render(&self) -> String {
+        let number_of_stacks = self.dock.keys().count() as u32;
+        let height = self.dock.values().map(|s| s.len()).max().unwrap_or(0);
+        let mut rows = Vec::new();
+        for level in (0..height).rev() {
+            let row = (1..=number_of_stacks)
+                .map(|i| match self.dock[&i].get(level) {
+                    Some(c) => format!("[{c}]"),
+                    None => "   ".to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            rows.push(row.trim_end().to_string());
+        }
+        let numbers = (1..=number_of_stacks)
+            .map(|i| format!(" {i} "))
+            .collect::<Vec<_>>()
+            .join(" ");
+        rows.push(numbers.trim_end().to_string());
+        rows.join("\n") + "\n"
+    }
+}
+
+pub fn solve_part1(input: &Input) -> String {
+    let mut crane = Crane::new(input, Model::CrateMover9000);
+    crane.run();
+    crane.top_crates()
+}
+
+pub fn solve_part2(input: &Input) -> String {
+    let mut crane = Crane::new(input, Model::CrateMover9001);
+    crane.run();
+    crane.top_crates()
+}
+
+// nine stacks of crates and `moves` moves, each of which only moves crates
+// that are there to move
+pub fn generate(rng: &mut Rng, moves: usize) -> String {
+    const STACKS: usize = 9;
+    let mut heights = (0..STACKS)
+        .map(|_| rng.range(1..=8) as usize)
+        .collect::<Vec<_>>();
+
+    let mut input = String::new();
+    let tallest = *heights.iter().max().unwrap();
+    for level in (0..tallest).rev() {
+        let row = heights
+            .iter()
+            .map(|&height| {
+                if level < height {
+                    format!("[{}]", (b'A' + rng.below(26) as u8) as char)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        input += &row.join(" ");
+        input.push('\n');
+    }
+    let numbers = (1..=STACKS).map(|i| format!(" {i} ")).collect::<Vec<_>>();
+    input += &numbers.join(" ");
+    input += "\n\n";
+
+    for _ in 0..moves {
+        let from = loop {
+            let stack = rng.below(STACKS);
+            if heights[stack] > 0 {
+                break stack;
+            }
+        };
+        let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+        let number = rng.range(1..=heights[from].min(10) as i64) as usize;
+        heights[from] -= number;
+        heights[to] += number;
+        input += &format!("move {number} from {} to {}\n", from + 1, to + 1);
+    }
+    input
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Part1Input = Input;
+    type Part2Input = Input;
+    type Part1Output = String;
+    type Part2Output = String;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_parsing() {
+        let input = r#"
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+"#;
+
+        let parsed_input = input_generator_part1(input).unwrap();
+        let expected = Input {
+            starting_layout: HashMap::from([
+                (1, vec!['Z', 'N']),
+                (2, vec!['M', 'C', 'D']),
+                (3, vec!['P']),
+            ]),
+            instructions: vec![
+                Instruction {
+                    number_to_move: 1,
+                    start_stack: 2,
+                    end_stack: 1,
+                },
+                Instruction {
+                    number_to_move: 3,
+                    start_stack: 1,
+                    end_stack: 3,
+                },
+                Instruction {
+                    number_to_move: 2,
+                    start_stack: 2,
+                    end_stack: 1,
+                },
+                Instruction {
+                    number_to_move: 1,
+                    start_stack: 1,
+                    end_stack: 2,
+                },
+            ],
+        };
+
+        assert_eq!(parsed_input, expected);
+    }
+
+    #[test]
+    fn test_move_from_missing_stack() {
+        let input = r#"
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 4 to 1
+"#;
+
+        let error = input_generator_part1(input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 13));
+        assert_eq!(error.expected, "the number of a stack");
+    }
+
+    #[test]
+    fn test_crane_steps() {
+        let input = r#"
+    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+"#;
+
+        let input = input_generator_part1(input).unwrap();
+        let mut crane = Crane::new(&input, Model::CrateMover9000);
+        assert!(crane.step());
+        assert_eq!(crane.render(), "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n");
+        assert!(crane.step());
+        assert_eq!(
+            crane.render(),
+            "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3\n"
+        );
+        assert!(!crane.step());
+        assert_eq!((crane.steps(), crane.top_crates().as_str()), (2, "CZ"));
+    }
+}
@@ -0,0 +1,116 @@
+//! Day 6: Tuning Trouble.
+//!
+//! Finds the first start-of-packet (4 characters) and start-of-message
+//! (14 characters) markers in a datastream.
+
+use aoc_common::{parse, rng::Rng, NoParams, ParseError, Solution};
+use std::collections::HashSet;
+
+pub fn all_different(chunk: &[char]) -> bool {
+    chunk.iter().collect::<HashSet<_>>().len() == chunk.len()
+}
+
+// how many characters in a row have to be different to make each marker
+pub const PACKET_MARKER: usize = 4;
+pub const MESSAGE_MARKER: usize = 14;
+
+// how many characters have come in once the first marker `size` long has -
+// the index of the first chunk that has all different chars in it, plus the
+// chunk itself
+pub fn find_marker(datastream: &str, size: usize) -> Option<usize> {
+    datastream
+        .chars()
+        .collect::<Vec<_>>()
+        .windows(size)
+        .position(all_different)
+        .map(|index| index + size)
+}
+
+// the datastream is a single line of lowercase letters, with a marker `size`
+// long somewhere in it
+fn parse_datastream(input: &str, size: usize) -> Result<String, ParseError> {
+    let mut lines = parse::lines(Day06::DAY, input);
+    let mut line = lines.expect("a datastream")?;
+    let datastream = line.take_while(|c| c.is_ascii_lowercase());
+    if find_marker(datastream, size).is_none() {
+        return Err(line.error(format!("a marker ({size} different letters in a row)")));
+    }
+    line.end()?;
+    lines.end()?;
+    Ok(datastream.to_owned())
+}
+
+pub fn input_generator(input: &str) -> Result<String, ParseError> {
+    parse_datastream(input, PACKET_MARKER)
+}
+
+pub fn input_generator_part2(input: &str) -> Result<String, ParseError> {
+    parse_datastream(input, MESSAGE_MARKER)
+}
+
+pub fn solve_part1(input: &str) -> usize {
+    find_marker(input, PACKET_MARKER).expect("the parser checks there's a marker")
+}
+
+pub fn solve_part2(input: &str) -> usize {
+    find_marker(input, MESSAGE_MARKER).expect("the parser checks there's a marker")
+}
+
+// a datastream `length` characters long. everything before the last fourteen
+// only uses three letters, so neither marker turns up until the very end
+pub fn generate(rng: &mut Rng, length: usize) -> String {
+    let mut stream = (0..length.saturating_sub(14))
+        .map(|_| *rng.choose(&['a', 'b', 'c']))
+        .collect::<String>();
+    let mut marker = ('d'..='z').collect::<Vec<_>>();
+    rng.shuffle(&mut marker);
+    stream.extend(&marker[..14]);
+    stream.push('\n');
+    stream
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+
+    type Part1Input = String;
+    type Part2Input = String;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part2(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day06);
+
+#[test]
+fn test_day06_no_marker() {
+    let error = input_generator("abcabcabc\n").err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (6, 1, 10));
+    assert_eq!(error.expected, "a marker (4 different letters in a row)");
+
+    // a packet marker but no message marker
+    assert!(input_generator("abcd\n").is_ok());
+    let error = input_generator_part2("abcd\n").err().unwrap();
+    assert_eq!(error.expected, "a marker (14 different letters in a row)");
+}
@@ -1,65 +1,13 @@
-use std::collections::HashSet;
-
-fn all_different(chunk: &[char]) -> bool {
-    chunk.iter().collect::<HashSet<_>>().len() == chunk.len()
-}
-
-pub fn solve_part1(input: &str) -> usize {
-    // find the 0-based index of the first 4-char chunk that has four different chars in it
-    // then add 4 (for the chunk itself)
-    input
-        .chars()
-        .collect::<Vec<_>>()
-        .windows(4)
-        .enumerate()
-        .find(|(_, val)| all_different(val))
-        .unwrap()
-        .0
-        + 4
-}
-
-pub fn solve_part2(input: &str) -> usize {
-    // same as before, but 14
-    input
-        .chars()
-        .collect::<Vec<_>>()
-        .windows(14)
-        .enumerate()
-        .find(|(_, val)| all_different(val))
-        .unwrap()
-        .0
-        + 14
-}
+use day06::{input_generator, input_generator_part2, solve_part1, solve_part2};
 
 fn main() {
-    let input = include_str!("../input.txt");
+    let puzzle_input = aoc_common::input::from_args_or_exit();
+    let input_1 = aoc_common::input::parse_or_exit(input_generator(&puzzle_input));
+    let part_1 = solve_part1(&input_1);
 
-    let part_1 = solve_part1(input);
-    let part_2  = solve_part2(input);
+    let input_2 = aoc_common::input::parse_or_exit(input_generator_part2(&puzzle_input));
+    let part_2  = solve_part2(&input_2);
 
     println!("Part 1: {}", part_1);
     println!("Part 2: {}", part_2);
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part1() {
-        assert_eq!(solve_part1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!(solve_part1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!(solve_part1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!(solve_part1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
-    }
-
-    #[test]
-    fn test_part2() {
-        assert_eq!(solve_part2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!(solve_part2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!(solve_part2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!(solve_part2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!(solve_part2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
-    }
-}
-
@@ -0,0 +1,255 @@
+//! Day 7: No Space Left On Device.
+//!
+//! Rebuilds the [`Directory`] sizes from a terminal session, then finds the small
+//! directories and the smallest one worth deleting.
+
+use aoc_common::{parse, rng::Rng, ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub struct Directory {
+    size_of_files: usize,
+    // fully-qualified
+    subdirectory_names: Vec<String>,
+}
+
+impl Directory {
+    pub fn new() -> Self {
+        Self {
+            size_of_files: 0,
+            subdirectory_names: Vec::new(),
+        }
+    }
+
+    pub fn total_size(&self, directory_tree: &HashMap<String, Directory>) -> usize {
+        self.subdirectory_names
+            .iter()
+            .map(|d| directory_tree.get(d).unwrap().total_size(directory_tree))
+            .sum::<usize>()
+            + self.size_of_files
+    }
+}
+
+impl Default for Directory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<HashMap<String, usize>, ParseError> {
+    let mut directories: HashMap<String, Directory> =
+        HashMap::from([(String::from(""), Directory::new())]);
+    // the current directory we're in - use "" for the root, instead of "/", to avoid concatenation weirdness
+    let mut current_path = String::from("");
+    for mut line in parse::lines(Day07::DAY, input) {
+        if line.is_empty() {
+            continue;
+        }
+        if line.try_tag("$ ") {
+            let command = line.one_of(&[("ls", false), ("cd ", true)])?;
+            if !command {
+                line.end()?;
+                continue;
+            }
+
+            // we're cd-ing somewhere, so we need to adjust our current location
+            let new_location = line.take_while1("a directory", |c| !c.is_whitespace())?;
+            line.end()?;
+            if new_location == ".." {
+                // up a directory - pop off the last path component
+                let Some((new_path, _)) = current_path.rsplit_once('/') else {
+                    return Err(line.error_at(5, "a subdirectory (we're already at the root)"));
+                };
+                current_path = new_path.to_owned();
+            } else if new_location == "/" {
+                // back to the root - replace everything
+                current_path = String::from("");
+            } else {
+                let new_path = current_path + "/" + new_location;
+                // it has to have turned up in an `ls` already, or nothing would count its size
+                if !directories.contains_key(&new_path) {
+                    return Err(line.error_at(5, "a directory that's been listed by `ls`"));
+                }
+                current_path = new_path;
+            }
+            continue;
+        }
+
+        // otherwise this is the output from ls
+        let current_directory = directories.get_mut(&current_path).unwrap();
+        if line.try_tag("dir ") {
+            // this directory has a subdirectory - remember the (full) path of it
+            let name = line.take_while1("a directory name", |c| !c.is_whitespace())?;
+            line.end()?;
+            let subdirectory = current_path.clone() + "/" + name;
+            current_directory
+                .subdirectory_names
+                .push(subdirectory.clone());
+            // it might never get cd-ed into, but it still needs to be there to add up sizes
+            directories.entry(subdirectory).or_default();
+        } else {
+            // this directory contains a file - add its size to the running total for this dir
+            let size = match line.number::<usize>() {
+                Ok(size) => size,
+                Err(_) => return Err(line.error("`$`, `dir` or a file size")),
+            };
+            line.tag(" ")?;
+            line.take_while1("a file name", |c| !c.is_whitespace())?;
+            line.end()?;
+            current_directory.size_of_files += size;
+        }
+    }
+
+    Ok(directories
+        .iter()
+        .map(|(name, d)| (name.clone(), d.total_size(&directories)))
+        .collect())
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    // directories at most this big count towards part 1
+    pub small_directory_size: usize,
+    pub disk_size: usize,
+    // how much free space the update needs
+    pub space_needed: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            small_directory_size: 100_000,
+            disk_size: 70_000_000,
+            space_needed: 30_000_000,
+        }
+    }
+}
+
+pub fn solve_part1(input: &HashMap<String, usize>, params: &Params) -> usize {
+    input
+        .values()
+        .filter(|&v| v <= &params.small_directory_size)
+        .sum()
+}
+
+pub fn solve_part2(input: &HashMap<String, usize>, params: &Params) -> usize {
+    let root_directory_size = input.get("").unwrap();
+    let already_free_size = params.disk_size - root_directory_size;
+    let extra_space_needed = params.space_needed - already_free_size;
+    *input
+        .values()
+        .filter(|&v| v >= &extra_space_needed)
+        .min()
+        .unwrap()
+}
+
+// a terminal session that explores `directories` directories. the files add up
+// to between 40 and 45 million, so the update always needs something deleting -
+// and deleting everything would always be enough
+pub fn generate(rng: &mut Rng, directories: usize) -> String {
+    fn word(rng: &mut Rng) -> String {
+        (0..rng.range(1..=8))
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect()
+    }
+
+    let directories = directories.max(1);
+    let mut names = vec![String::from("/")];
+    let mut children = vec![Vec::new(); directories];
+    for directory in 1..directories {
+        let parent = rng.below(directory);
+        let name = loop {
+            let name = word(rng);
+            if !children[parent].iter().any(|&c| names[c] == name) {
+                break name;
+            }
+        };
+        names.push(name);
+        children[parent].push(directory);
+    }
+
+    // how big each file is compared to the others - scaled up to the total below
+    let files = (0..directories)
+        .map(|directory| {
+            let least = if directory == 0 { 1 } else { 0 };
+            (0..rng.range(least..=5))
+                .map(|_| {
+                    (
+                        rng.range(1..=1000) as usize,
+                        format!("{}.{}", word(rng), word(rng)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let weight = files.iter().flatten().map(|(w, _)| w).sum::<usize>();
+    let total = rng.range(40_000_000..=45_000_000) as usize;
+
+    // depth first, with None for coming back up a level
+    let mut input = String::from("$ cd /\n");
+    let mut to_visit = vec![Some(0)];
+    while let Some(next) = to_visit.pop() {
+        let Some(directory) = next else {
+            input += "$ cd ..\n";
+            continue;
+        };
+        if directory != 0 {
+            input += &format!("$ cd {}\n", names[directory]);
+        }
+        input += "$ ls\n";
+        for &child in &children[directory] {
+            input += &format!("dir {}\n", names[child]);
+        }
+        for (w, name) in &files[directory] {
+            input += &format!("{} {name}\n", w * total / weight);
+        }
+        for &child in children[directory].iter().rev() {
+            to_visit.push(None);
+            to_visit.push(Some(child));
+        }
+    }
+    input
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Part1Input = HashMap<String, usize>;
+    type Part2Input = HashMap<String, usize>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input, params)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input, params)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day07);
+
+#[test]
+fn test_day07_cd_to_unknown_directory() {
+    let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ cd b\n";
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (7, 5, 6));
+    assert_eq!(error.expected, "a directory that's been listed by `ls`");
+    assert_eq!(error.found, "`b`");
+}
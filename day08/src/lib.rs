@@ -0,0 +1,142 @@
+//! Day 8: Treetop Tree House.
+//!
+//! A [`Forest`] of [`Tree`] heights - count the trees visible from outside, and find
+//! the best scenic score.
+
+use aoc_common::{rng::Rng, Grid, NoParams, ParseError, Solution};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Tree {
+    height: u32,
+}
+
+pub type Forest = Grid<Tree>;
+
+pub fn is_tree_visible(
+    (row, column): (&[Tree], &[Tree]),
+    (row_index, column_index): (usize, usize),
+) -> bool {
+    let tree_height = row[row_index].height;
+    let (left, tree_onwards_right) = row.split_at(row_index);
+    let (up, tree_onwards_down) = column.split_at(column_index);
+
+    all_shorter(left, tree_height)
+        || all_shorter(&tree_onwards_right[1..], tree_height)
+        || all_shorter(up, tree_height)
+        || all_shorter(&tree_onwards_down[1..], tree_height)
+}
+
+fn all_shorter<'a, I>(trees: I, height: u32) -> bool
+where
+    I: IntoIterator<Item = &'a Tree>,
+{
+    trees.into_iter().all(|t| t.height < height)
+}
+
+fn count_until<'a, I>(trees: I, height: u32) -> u32
+where
+    I: IntoIterator<Item = &'a Tree>,
+{
+    // take_while + 1 would be right for inner trees, but wrong for the edge - just loop instead
+    let mut count = 0;
+    for t in trees {
+        count += 1;
+        if t.height >= height {
+            break;
+        }
+    }
+    count
+}
+
+pub fn scenic_score(
+    (row, column): (&[Tree], &[Tree]),
+    (row_index, column_index): (usize, usize),
+) -> u32 {
+    let tree_height = row[row_index].height;
+    let (left, tree_onwards_right) = row.split_at(row_index);
+    let (up, tree_onwards_down) = column.split_at(column_index);
+
+    let left_distance = count_until(left.iter().rev(), tree_height);
+    let right_distance = count_until(&tree_onwards_right[1..], tree_height);
+    let up_distance = count_until(up.iter().rev(), tree_height);
+    let down_distance = count_until(&tree_onwards_down[1..], tree_height);
+
+    left_distance * right_distance * up_distance * down_distance
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Forest, ParseError> {
+    Grid::parse(Day08::DAY, input, "a tree height (0-9)", |c| {
+        c.to_digit(10).map(|height| Tree { height })
+    })
+}
+
+pub fn solve_part1(input: &Forest) -> usize {
+    (0..input.width())
+        .map(|r| {
+            let column = input.column(r).copied().collect::<Vec<_>>();
+            (0..input.height())
+                .filter(|&c| is_tree_visible((input.row(c), &column), (r, c)))
+                .count()
+        })
+        .sum()
+}
+
+pub fn solve_part2(input: &Forest) -> u32 {
+    (0..input.width())
+        .map(|r| {
+            let column = input.column(r).copied().collect::<Vec<_>>();
+            (0..input.height())
+                .map(|c| scenic_score((input.row(c), &column), (r, c)))
+                .max()
+                .unwrap()
+        })
+        .max()
+        .unwrap()
+}
+
+// a `size` by `size` patch of trees
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut row = (0..size)
+                .map(|_| (b'0' + rng.below(10) as u8) as char)
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+
+    type Part1Input = Forest;
+    type Part2Input = Forest;
+    type Part1Output = usize;
+    type Part2Output = u32;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day08);
@@ -0,0 +1,244 @@
+//! Day 9: Rope Bridge.
+//!
+//! Follows a rope through its [`Instruction`]s, counting the squares the tail
+//! visits - with two knots in part 1 and ten in part 2.
+
+use aoc_common::{parse, rng::Rng, NoParams, ParseError, Simulation, Solution};
+use std::collections::HashSet;
+
+#[derive(Copy, Clone)]
+pub enum Direction {
+    Up,
+    Down,
+    Right,
+    Left,
+}
+
+pub struct Instruction {
+    direction: Direction,
+    amount: usize,
+}
+
+pub fn move_one((start_x, start_y): (i32, i32), direction: &Direction) -> (i32, i32) {
+    match direction {
+        Direction::Up => (start_x, start_y + 1),
+        Direction::Down => (start_x, start_y - 1),
+        Direction::Left => (start_x - 1, start_y),
+        Direction::Right => (start_x + 1, start_y),
+    }
+}
+
+pub fn find_new_tail(
+    tail @ (tail_x, tail_y): (i32, i32),
+    (head_x, head_y): (i32, i32),
+) -> (i32, i32) {
+    let x_distance_moved = (tail_x - head_x).abs();
+    let y_distance_moved = (tail_y - head_y).abs();
+    // if the head is at most one away, the tail doesn't move
+    if x_distance_moved <= 1 && y_distance_moved <= 1 {
+        return tail;
+    }
+
+    match (x_distance_moved, y_distance_moved) {
+        // moved diagonally - move both components 1 in the right direction
+        (2, 2) => {
+            let new_tail_x = if tail_x < head_x {
+                tail_x + 1
+            } else {
+                tail_x - 1
+            };
+            let new_tail_y = if tail_y < head_y {
+                tail_y + 1
+            } else {
+                tail_y - 1
+            };
+            (new_tail_x, new_tail_y)
+        }
+        // only one dimension has moved 2 - tail moves one in that direction; the other dimension ends up the same as where the head has gone
+        (2, 0 | 1) if tail_x < head_x => (tail_x + 1, head_y),
+        (2, 0 | 1) if tail_x > head_x => (tail_x - 1, head_y),
+        (0 | 1, 2) if tail_y < head_y => (head_x, tail_y + 1),
+        (0 | 1, 2) if tail_y > head_y => (head_x, tail_y - 1),
+        // the previous knot can never have moved more than 2
+        // and we handled the cases <2 higher up
+        _ => unreachable!(),
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(Day09::DAY, input)
+        .map(|mut l| {
+            let direction = l.one_of(&[
+                ("U", Direction::Up),
+                ("D", Direction::Down),
+                ("L", Direction::Left),
+                ("R", Direction::Right),
+            ])?;
+            l.tag(" ")?;
+            let position = l.position();
+            let amount = l.number::<i32>()?;
+            l.end()?;
+            let Ok(amount) = usize::try_from(amount) else {
+                return Err(l.error_at(position, "a number of steps that isn't negative"));
+            };
+            Ok(Instruction { direction, amount })
+        })
+        .collect()
+}
+
+// pulls the head of a rope through the instructions, a square at a time
+pub struct Rope {
+    moves: Vec<Direction>,
+    moved: usize,
+    // the head first, the tail last
+    knots: Vec<(i32, i32)>,
+    tail_visited: HashSet<(i32, i32)>,
+}
+
+impl Rope {
+    pub fn new(instructions: &[Instruction], knots: usize) -> Self {
+        let moves = instructions
+            .iter()
+            .flat_map(|i| std::iter::repeat_n(i.direction, i.amount))
+            .collect();
+        Self {
+            moves,
+            moved: 0,
+            knots: vec![(0, 0); knots],
+            tail_visited: HashSet::from([(0, 0)]),
+        }
+    }
+
+    pub fn knots(&self) -> &[(i32, i32)] {
+        &self.knots
+    }
+
+    pub fn tail_visited(&self) -> &HashSet<(i32, i32)> {
+        &self.tail_visited
+    }
+}
+
+impl Simulation for Rope {
+    fn step(&mut self) -> bool {
+        let Some(direction) = self.moves.get(self.moved) else {
+            return false;
+        };
+
+        self.knots[0] = move_one(self.knots[0], direction);
+        for i in 1..self.knots.len() {
+            self.knots[i] = find_new_tail(self.knots[i], self.knots[i - 1]);
+        }
+        self.tail_visited.insert(*self.knots.last().unwrap());
+        self.moved += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.moved
+    }
+
+    // the way the puzzle draws it - the head, numbered knots and the tail, with the
+    // start and the squares the tail has been to underneath. y goes up the page
+    fn render(&self) -> String {
+        let points = self.knots.iter().chain(&self.tail_visited);
+        let min_x = points.clone().map(|k| k.0).min().unwrap();
+        let max_x = points.clone().map(|k| k.0).max().unwrap();
+        let min_y = points.clone().map(|k| k.1).min().unwrap();
+        let max_y = points.map(|k| k.1).max().unwrap();
+
+        let mut rendered = String::new();
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                let knot = self.knots.iter().position(|&k| k == (x, y));
+                rendered.push(match knot {
+                    Some(0) => 'H',
+                    Some(i) if i == self.knots.len() - 1 => 'T',
+                    Some(i) => char::from_digit(i as u32, 10).unwrap_or('*'),
+                    None if (x, y) == (0, 0) => 's',
+                    None if self.tail_visited.contains(&(x, y)) => '#',
+                    None => '.',
+                });
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+pub fn solve_part1(input: &[Instruction]) -> usize {
+    let mut rope = Rope::new(input, 2);
+    rope.run();
+    rope.tail_visited().len()
+}
+
+pub fn solve_part2(input: &[Instruction]) -> usize {
+    let mut rope = Rope::new(input, 10);
+    rope.run();
+    rope.tail_visited().len()
+}
+
+// `motions` moves of the head, of up to 20 steps each
+pub fn generate(rng: &mut Rng, motions: usize) -> String {
+    (0..motions)
+        .map(|_| {
+            format!(
+                "{} {}\n",
+                rng.choose(&['U', 'D', 'L', 'R']),
+                rng.range(1..=20)
+            )
+        })
+        .collect()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Part1Input = Vec<Instruction>;
+    type Part2Input = Vec<Instruction>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day09);
+
+#[test]
+fn test_rope_render() {
+    let input = input_generator_part1("R 4\nU 2\n").unwrap();
+    let mut rope = Rope::new(&input, 10);
+    assert!(rope.run_for(4));
+    assert_eq!(rope.render(), "4321H\n");
+    rope.run();
+    assert_eq!(rope.steps(), 6);
+    assert_eq!(rope.render(), "....H\n.4321\n5....\n");
+}
+
+#[test]
+fn test_day09_negative_steps() {
+    let error = input_generator_part1("R 4\nU -2\n").err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (9, 2, 3));
+    assert_eq!(error.expected, "a number of steps that isn't negative");
+    assert_eq!(error.found, "`-2`");
+}
@@ -0,0 +1,292 @@
+//! Day 10: Cathode-Ray Tube.
+//!
+//! Runs the CPU [`Instruction`]s, sampling the signal strength for part 1 and
+//! drawing the CRT's output for part 2.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    ParseError, Simulation, Solution,
+};
+use serde::{Deserialize, Serialize};
+
+pub enum Instruction {
+    Addx(i32),
+    Noop,
+}
+
+impl Instruction {
+    pub fn parse(mut line: Line) -> Result<Self, ParseError> {
+        // (whether there's a number to add)
+        let instruction = if line.one_of(&[("noop", false), ("addx", true)])? {
+            line.tag(" ")?;
+            Self::Addx(line.number()?)
+        } else {
+            Self::Noop
+        };
+        line.end()?;
+        Ok(instruction)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum State {
+    Beginning,
+    StillAdding,
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::lines(Day10::DAY, input)
+        .map(Instruction::parse)
+        .collect()
+}
+
+// which cycles' signal strengths get added up in part 1
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    pub first_sample: i32,
+    pub sample_every: i32,
+    pub samples: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            first_sample: 20,
+            sample_every: 40,
+            samples: 6,
+        }
+    }
+}
+
+// runs the program a clock cycle at a time
+pub struct Cpu<'a> {
+    instructions: &'a [Instruction],
+    next_instruction: usize,
+    state: State,
+    register: i32,
+    cycles: usize,
+}
+
+impl<'a> Cpu<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Self {
+        Self {
+            instructions,
+            next_instruction: 0,
+            state: State::Beginning,
+            register: 1,
+            cycles: 0,
+        }
+    }
+
+    // the cycle that's underway - they count from 1
+    pub fn cycle(&self) -> i32 {
+        self.cycles as i32 + 1
+    }
+
+    // the X register, during the current cycle
+    pub fn x(&self) -> i32 {
+        self.register
+    }
+
+    // the program's run out of instructions
+    pub fn halted(&self) -> bool {
+        self.next_instruction == self.instructions.len()
+    }
+}
+
+impl Simulation for Cpu<'_> {
+    // finish the current cycle
+    fn step(&mut self) -> bool {
+        let Some(instruction) = self.instructions.get(self.next_instruction) else {
+            return false;
+        };
+
+        match (self.state, instruction) {
+            (State::Beginning, Instruction::Noop) => {
+                // nothing more to do; fetch the next instruction
+                self.next_instruction += 1;
+            }
+            (State::Beginning, Instruction::Addx(_)) => {
+                // this takes two cycles, so we need to enter the still-adding state for next time round
+                self.state = State::StillAdding;
+            }
+            (State::StillAdding, Instruction::Addx(value)) => {
+                // this is our second cycle of the add, so finish it off
+                self.register += value;
+                self.state = State::Beginning;
+                self.next_instruction += 1;
+            }
+            _ => unreachable!(),
+        }
+        self.cycles += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.cycles
+    }
+
+    fn render(&self) -> String {
+        let running = match (self.instructions.get(self.next_instruction), self.state) {
+            (None, _) => "halted".to_string(),
+            (Some(Instruction::Noop), _) => "noop".to_string(),
+            (Some(Instruction::Addx(v)), State::Beginning) => format!("addx {v} (1/2)"),
+            (Some(Instruction::Addx(v)), State::StillAdding) => format!("addx {v} (2/2)"),
+        };
+        format!("cycle {}: X = {}, {running}\n", self.cycle(), self.x())
+    }
+}
+
+pub fn solve_part1(input: &[Instruction], params: &Params) -> i32 {
+    let mut cpu = Cpu::new(input);
+    let mut signal_strength = 0;
+    for sample in 0..params.samples {
+        let cycle = params.first_sample + params.sample_every * sample as i32;
+        // only cycles where the program's still running count
+        if !cpu.run_until(|cpu| cpu.cycle() == cycle) || cpu.halted() {
+            break;
+        }
+        signal_strength += cycle * cpu.x();
+    }
+
+    signal_strength
+}
+
+pub fn sprite_overlaps(sprite_center: i32, location: i32) -> bool {
+    sprite_center == location || sprite_center - 1 == location || sprite_center + 1 == location
+}
+
+pub const CRT_WIDTH: usize = 40;
+pub const CRT_HEIGHT: usize = 6;
+
+// draws a pixel each cycle, wherever the CPU's X register puts the sprite
+pub struct Crt<'a> {
+    cpu: Cpu<'a>,
+    pixels: Vec<bool>,
+}
+
+impl<'a> Crt<'a> {
+    pub fn new(instructions: &'a [Instruction]) -> Self {
+        Self {
+            cpu: Cpu::new(instructions),
+            pixels: Vec::with_capacity(CRT_WIDTH * CRT_HEIGHT),
+        }
+    }
+
+    pub fn cpu(&self) -> &Cpu<'a> {
+        &self.cpu
+    }
+}
+
+impl Simulation for Crt<'_> {
+    fn step(&mut self) -> bool {
+        if self.pixels.len() == CRT_WIDTH * CRT_HEIGHT {
+            return false;
+        }
+
+        let column = (self.pixels.len() % CRT_WIDTH) as i32;
+        self.pixels.push(sprite_overlaps(self.cpu.x(), column));
+        // once the program's finished, X just stays where it is
+        self.cpu.step();
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.pixels.len()
+    }
+
+    // the screen so far - the row being drawn stops at the beam
+    fn render(&self) -> String {
+        let mut rendered = String::new();
+        for row in self.pixels.chunks(CRT_WIDTH) {
+            rendered.extend(row.iter().map(|&lit| if lit { '#' } else { '.' }));
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+pub fn solve_part2(input: &[Instruction]) -> String {
+    let mut crt = Crt::new(input);
+    crt.run();
+    format!("\n{}", crt.render())
+}
+
+// at least `instructions` instructions, and always enough to last the 240 cycles
+// the CRT takes to draw. X wanders about, but stays near the screen
+pub fn generate(rng: &mut Rng, instructions: usize) -> String {
+    let mut input = String::new();
+    let (mut x, mut cycles) = (1, 0);
+    for written in 0.. {
+        if written >= instructions && cycles >= 240 {
+            break;
+        }
+        if rng.one_in(3) {
+            input += "noop\n";
+            cycles += 1;
+        } else {
+            let change = (rng.range(-1..=40) - x).clamp(-15, 15);
+            x += change;
+            input += &format!("addx {change}\n");
+            cycles += 2;
+        }
+    }
+    input
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Part1Input = Vec<Instruction>;
+    type Part2Input = Vec<Instruction>;
+    type Part1Output = i32;
+    type Part2Output = String;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input, params)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day10);
+
+#[test]
+fn test_cpu_cycles() {
+    let input = input_generator_part1("noop\naddx 3\naddx -5\n").unwrap();
+    let mut cpu = Cpu::new(&input);
+    assert!(cpu.run_until(|cpu| cpu.cycle() == 3));
+    assert_eq!(cpu.render(), "cycle 3: X = 1, addx 3 (2/2)\n");
+    assert!(cpu.step());
+    assert_eq!(cpu.x(), 4);
+
+    cpu.run();
+    assert_eq!((cpu.steps(), cpu.x()), (5, -1));
+    assert_eq!(cpu.render(), "cycle 6: X = -1, halted\n");
+}
+
+#[test]
+fn test_day10_addx_without_a_number() {
+    let error = input_generator_part1("noop\naddx\n").err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (10, 2, 5));
+    assert_eq!(error.expected, "a space");
+    assert_eq!(error.found, "end of line");
+}
@@ -0,0 +1,290 @@
+//! Day 11: Monkey in the Middle.
+//!
+//! Simulates the [`Monkey`]s throwing items about, for 20 rounds with worry
+//! relief and 10,000 without.
+
+use aoc_common::{
+    parse::{self, Lines},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use std::{cmp::Reverse, collections::HashMap};
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Operand {
+    OldValue,
+    SpecificValue(u128),
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Monkey {
+    starting_items: Vec<u128>,
+    operation: char,
+    operand: Operand,
+    divisibility_check: u128,
+    true_monkey_id: usize,
+    false_monkey_id: usize,
+}
+
+// the next line of a monkey's description, with any indentation skipped
+fn monkey_line<'a>(lines: &mut Lines<'a>, tag: &str) -> Result<parse::Line<'a>, ParseError> {
+    let mut line = lines.expect(&format!("`{}`", tag.trim()))?;
+    line.skip_whitespace();
+    line.tag(tag)?;
+    Ok(line)
+}
+
+pub fn input_generator_part1(input: &str) -> Result<HashMap<usize, Monkey>, ParseError> {
+    let monkeys = parse::blocks(Day11::DAY, input);
+
+    let mut monkey_dict = HashMap::new();
+    // monkeys they throw to, which need checking once we've seen them all
+    let mut throw_targets = Vec::new();
+
+    for (index, mut monkey_lines) in monkeys.into_iter().enumerate() {
+        let mut line = monkey_line(&mut monkey_lines, "Monkey ")?;
+        // they're numbered in order, which the monkey loop relies on
+        let monkey_id = line.number::<usize>()?;
+        if monkey_id != index {
+            return Err(line.error_at(7, format!("monkey `{index}`")));
+        }
+        line.tag(":")?;
+        line.end()?;
+
+        let mut line = monkey_line(&mut monkey_lines, "Starting items: ")?;
+        let mut monkey_items = vec![line.number()?];
+        while line.try_tag(", ") {
+            monkey_items.push(line.number()?);
+        }
+        line.end()?;
+
+        let mut line = monkey_line(&mut monkey_lines, "Operation: new = old ")?;
+        let operation = line.one_of(&[("*", '*'), ("+", '+')])?;
+        line.tag(" ")?;
+        let operand = if line.try_tag("old") {
+            Operand::OldValue
+        } else {
+            Operand::SpecificValue(line.number()?)
+        };
+        line.end()?;
+
+        let mut line = monkey_line(&mut monkey_lines, "Test: divisible by ")?;
+        let divisibility_test = line.number()?;
+        line.end()?;
+
+        let mut monkey_targets = Vec::new();
+        for tag in ["If true: throw to monkey ", "If false: throw to monkey "] {
+            let mut line = monkey_line(&mut monkey_lines, tag)?;
+            let position = line.position();
+            let target = line.number::<usize>()?;
+            line.end()?;
+            throw_targets.push((target, line.error_at(position, "a monkey that exists")));
+            monkey_targets.push(target);
+        }
+        monkey_lines.end()?;
+
+        monkey_dict.insert(
+            monkey_id,
+            Monkey {
+                starting_items: monkey_items,
+                operation,
+                operand,
+                divisibility_check: divisibility_test,
+                true_monkey_id: monkey_targets[0],
+                false_monkey_id: monkey_targets[1],
+            },
+        );
+    }
+
+    // the answers multiply the two busiest monkeys together
+    if monkey_dict.len() < 2 {
+        return Err(parse::lines(Day11::DAY, input)
+            .end_error("another monkey (there have to be at least two)"));
+    }
+
+    if let Some((_, error)) = throw_targets
+        .into_iter()
+        .find(|(target, _)| !monkey_dict.contains_key(target))
+    {
+        return Err(error);
+    }
+
+    Ok(monkey_dict)
+}
+
+pub fn run_monkey_loop<F>(
+    monkeys: &HashMap<usize, Monkey>,
+    iteration_count: usize,
+    post_inspection_operation: F,
+) -> HashMap<usize, usize>
+where
+    F: Fn(u128) -> u128,
+{
+    let monkey_count = monkeys.keys().len();
+    let mut monkeys: HashMap<_, _> = monkeys.clone();
+
+    let mut monkey_business = HashMap::new();
+
+    for _round_count in 1..=iteration_count {
+        // each monkey takes turns throwing
+        for monkey_id in 0..monkey_count {
+            let monkey = monkeys.get_mut(&monkey_id).unwrap();
+            let mut thrown_items_dict = HashMap::new();
+            for item in monkey.starting_items.iter() {
+                let inspected_item = match (monkey.operation, monkey.operand) {
+                    ('*', Operand::OldValue) => *item * *item,
+                    ('*', Operand::SpecificValue(v)) => *item * v,
+                    ('+', Operand::OldValue) => *item + *item,
+                    ('+', Operand::SpecificValue(v)) => *item + v,
+                    _ => unreachable!(),
+                };
+                // we inspected an item - remember this
+                *monkey_business.entry(monkey_id).or_insert(0usize) += 1;
+                let bored_item = post_inspection_operation(inspected_item);
+                let passed_check = bored_item.is_multiple_of(monkey.divisibility_check);
+                let monkey_id_to_throw_to = if passed_check {
+                    monkey.true_monkey_id
+                } else {
+                    monkey.false_monkey_id
+                };
+
+                let other_monkey = thrown_items_dict
+                    .entry(monkey_id_to_throw_to)
+                    .or_insert(Vec::new());
+                other_monkey.push(bored_item);
+            }
+
+            // this monkey has thrown all its stuff now
+            monkey.starting_items.clear();
+
+            for (monkey, mut items) in thrown_items_dict.into_iter() {
+                let monkey = monkeys.get_mut(&monkey).unwrap();
+                monkey.starting_items.append(&mut items);
+            }
+        }
+    }
+
+    monkey_business
+}
+
+pub fn solve_part1(input: &HashMap<usize, Monkey>) -> usize {
+    // post inspection: stop worrying, divide by 3 and lose remainder
+    let monkey_business = run_monkey_loop(input, 20, |i| i / 3);
+    let mut monkey_inspections = monkey_business.values().collect::<Vec<_>>();
+    monkey_inspections.sort_by_key(|i| Reverse(*i));
+    monkey_inspections[0] * monkey_inspections[1]
+}
+
+pub fn solve_part2(input: &HashMap<usize, Monkey>) -> usize {
+    // remember the product of all the checks
+    let monkey_factor = input
+        .values()
+        .map(|m| m.divisibility_check)
+        .product::<u128>();
+    // post inspection: stop number getting too big by taking it mod the product above
+    // this will keep every monkey throwing it to the right place
+    let monkey_business = run_monkey_loop(input, 10_000, |i| i % monkey_factor);
+    let mut monkey_inspections = monkey_business.values().collect::<Vec<_>>();
+    monkey_inspections.sort_by_key(|i| Reverse(*i));
+    monkey_inspections[0] * monkey_inspections[1]
+}
+
+// `monkeys` monkeys (at least two). only a couple of them multiply the worry
+// level, and never by itself - without the modulus, part 1's worry levels have
+// to stay small enough to fit
+pub fn generate(rng: &mut Rng, monkeys: usize) -> String {
+    const DIVISORS: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let monkeys = monkeys.max(2);
+    let multipliers = [rng.below(monkeys), rng.below(monkeys)];
+
+    let mut blocks = Vec::new();
+    for id in 0..monkeys {
+        let items = (0..rng.range(1..=8))
+            .map(|_| rng.range(50..=99).to_string())
+            .collect::<Vec<_>>();
+        let operation = if multipliers.contains(&id) {
+            format!("* {}", rng.range(2..=19))
+        } else {
+            format!("+ {}", rng.range(1..=8))
+        };
+        let mut others = (0..monkeys).filter(|&m| m != id).collect::<Vec<_>>();
+        rng.shuffle(&mut others);
+        let if_false = others.get(1).unwrap_or(&others[0]);
+        blocks.push(format!(
+            "Monkey {id}:\n  Starting items: {}\n  Operation: new = old {operation}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {if_false}\n",
+            items.join(", "),
+            rng.choose(&DIVISORS),
+            others[0],
+        ));
+    }
+    blocks.join("\n")
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Part1Input = HashMap<usize, Monkey>;
+    type Part2Input = HashMap<usize, Monkey>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day11);
+
+#[test]
+fn test_day11_bad_operation() {
+    let input = r#"Monkey 0:
+Starting items: 79, 98
+Operation: new = old / 19
+Test: divisible by 23
+  If true: throw to monkey 0
+  If false: throw to monkey 0
+"#;
+
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (11, 3, 22));
+    assert_eq!(error.expected, "`*` or `+`");
+    assert_eq!(error.found, "`/`");
+}
+
+#[test]
+fn test_day11_one_monkey() {
+    let input = r#"Monkey 0:
+Starting items: 79, 98
+Operation: new = old * 19
+Test: divisible by 23
+  If true: throw to monkey 0
+  If false: throw to monkey 0
+"#;
+
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (11, 7, 1));
+    assert_eq!(
+        error.expected,
+        "another monkey (there have to be at least two)"
+    );
+    assert_eq!(error.found, "end of input");
+}
@@ -0,0 +1,216 @@
+//! Day 12: Hill Climbing Algorithm.
+//!
+//! Shortest walks across a [`HeightMap`], from the start in part 1 and from any
+//! lowest square in part 2.
+
+use aoc_common::{
+    parse,
+    rng::Rng,
+    search::{self, SearchResult},
+    Grid, NoParams, ParseError, Solution,
+};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Elevation {
+    value: char,
+}
+
+pub type Coord = aoc_common::Coord<usize>;
+
+impl Elevation {
+    pub fn can_move_to(&self, next: &Elevation) -> bool {
+        (next.value as i32) - (self.value as i32) <= 1
+    }
+}
+
+#[derive(Debug)]
+pub struct HeightMap {
+    grid: Grid<Elevation>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Forwards,
+    Backwards,
+}
+
+impl HeightMap {
+    pub fn moves_from(&self, coord: Coord) -> Vec<Coord> {
+        self.grid.neighbours(coord).collect()
+    }
+
+    // the squares we can step to from here, going in this direction
+    pub fn steps_from(&self, coord: Coord, direction: Direction) -> Vec<Coord> {
+        self.moves_from(coord)
+            .into_iter()
+            .filter(|c| match direction {
+                Direction::Forwards => self.grid[coord].can_move_to(&self.grid[*c]),
+                Direction::Backwards => self.grid[*c].can_move_to(&self.grid[coord]),
+            })
+            .collect()
+    }
+
+    // every step is the same length, so a breadth-first search finds the
+    // shortest distance from start to everywhere we can get to
+    pub fn calculate_distances(
+        &self,
+        start: &Coord,
+        direction: Direction,
+    ) -> SearchResult<Coord, usize> {
+        search::bfs(*start, |&c| self.steps_from(c, direction))
+    }
+}
+
+pub struct Input {
+    map: HeightMap,
+    starting_coord: Coord,
+    ending_coord: Coord,
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Input, ParseError> {
+    let mut lines = parse::lines(Day12::DAY, input);
+    let squares = Grid::from_lines(&mut lines, "an elevation (a-z), `S` or `E`", |c| {
+        matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+    })?;
+    let start_coord = squares
+        .find(|&c| c == 'S')
+        .ok_or_else(|| lines.end_error("a starting point `S`"))?;
+    let end_coord = squares
+        .find(|&c| c == 'E')
+        .ok_or_else(|| lines.end_error("a best signal point `E`"))?;
+    let grid = squares.map(|&c| match c {
+        'S' => Elevation { value: 'a' },
+        'E' => Elevation { value: 'z' },
+        _ => Elevation { value: c },
+    });
+
+    let map = HeightMap { grid };
+
+    // both parts need a way up to the end - `S` is as low as it gets, so if
+    // there's one from there part 2 has one too
+    let distances = map.calculate_distances(&start_coord, Direction::Forwards);
+    if distances.distance_to(&end_coord).is_none() {
+        let end_line = parse::lines(Day12::DAY, input)
+            .nth(end_coord.y)
+            .expect("the end is on one of the lines");
+        return Err(end_line.error_at(end_coord.x, "an `E` that can be reached from `S`"));
+    }
+
+    Ok(Input {
+        map,
+        starting_coord: start_coord,
+        ending_coord: end_coord,
+    })
+}
+
+pub fn solve_part1(input: &Input) -> usize {
+    // we know where we're going, so head towards it - we can't get there in fewer
+    // steps than the manhattan distance. the parser's checked there's a way
+    let end = input.ending_coord;
+    let (_, result) = search::astar(
+        input.starting_coord,
+        |&c| {
+            input
+                .map
+                .steps_from(c, Direction::Forwards)
+                .into_iter()
+                .map(|n| (n, 1))
+        },
+        |c| c.manhattan_distance_to(&end),
+        |&c| c == end,
+    )
+    .expect("there's a path from the start to the end");
+    result.distance_to(&end).unwrap()
+}
+
+pub fn solve_part2(input: &Input) -> usize {
+    // same thing, but backwards from the end - for each point, find the shortest distance from it to the end
+    let distance_map = input
+        .map
+        .calculate_distances(&input.ending_coord, Direction::Backwards);
+    let lowest_elevation_squares = input
+        .map
+        .grid
+        .iter()
+        .filter(|(_, e)| e.value == 'a')
+        .map(|(c, _)| c)
+        .collect::<Vec<_>>();
+    lowest_elevation_squares
+        .into_iter()
+        .filter_map(|c| distance_map.distance_to(&c))
+        .min()
+        .unwrap()
+}
+
+// a hill `width` squares across (at least 26) that gets a letter higher every
+// column or so, with some too-steep rocks dotted about. the top row and the
+// first and last columns are kept clear, so there's always a way up
+pub fn generate(rng: &mut Rng, width: usize) -> String {
+    let width = width.max(26);
+    let height = (width / 4).max(5);
+    let start = rng.below(height);
+    let end = rng.below(height);
+
+    let mut input = String::new();
+    for y in 0..height {
+        for x in 0..width {
+            let elevation = (x * 26 / width) as u8;
+            let square = if x == 0 && y == start {
+                'S'
+            } else if x == width - 1 && y == end {
+                'E'
+            } else if y > 0 && x > 0 && x < width - 1 && elevation < 24 && rng.one_in(6) {
+                (b'a' + rng.range(elevation as i64 + 2..=25) as u8) as char
+            } else {
+                (b'a' + elevation) as char
+            };
+            input.push(square);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Part1Input = Input;
+    type Part2Input = Input;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day12);
+
+#[test]
+fn test_day12_no_path() {
+    // the `z` wall is too steep to climb from either side
+    let input = "Sazbc\nabzcE\n";
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (12, 2, 5));
+    assert_eq!(error.expected, "an `E` that can be reached from `S`");
+    assert_eq!(error.found, "`E`");
+}
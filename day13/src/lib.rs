@@ -0,0 +1,172 @@
+//! Day 13: Distress Signal.
+//!
+//! Packets parse into nested [`Value`]s, which are ordered the way the puzzle
+//! compares them.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map, map_res},
+    multi::separated_list0,
+    sequence::delimited,
+    Finish, IResult,
+};
+use std::cmp::Ordering;
+
+#[derive(Clone, PartialEq, Eq)]
+pub enum Value {
+    List(Vec<Value>),
+    Integer(i32),
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Integer(is), Value::Integer(io)) => is.cmp(io),
+            (Value::List(ls), Value::List(lo)) => ls.cmp(lo),
+            (left @ Value::List(_), Value::Integer(io)) => {
+                left.cmp(&Value::List(vec![Value::Integer(*io)]))
+            }
+            (Value::Integer(is), right @ Value::List(_)) => {
+                Value::List(vec![Value::Integer(*is)]).cmp(right)
+            }
+        }
+    }
+}
+
+fn parse_value(input: &str) -> IResult<&str, Value> {
+    let list = delimited(tag("["), separated_list0(tag(","), parse_value), tag("]"));
+    let parse_int = map_res(digit1, |x: &str| x.parse::<i32>());
+    alt((map(parse_int, Value::Integer), map(list, Value::List)))(input)
+}
+
+// a whole line is one packet
+fn parse_packet(line: Line) -> Result<Value, ParseError> {
+    let text = line.text();
+    match parse_value(text).finish() {
+        Ok(("", value)) => Ok(value),
+        Ok((rest, _)) => Err(line.error_at(text.len() - rest.len(), "end of line")),
+        // nom tells us what it had left when it gave up, which is where the problem is
+        Err(e) => Err(line.error_at(text.len() - e.input.len(), "a number, `[`, `,` or `]`")),
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<(Value, Value)>, ParseError> {
+    parse::blocks(Day13::DAY, input)
+        .into_iter()
+        .map(|mut p| {
+            let first = parse_packet(p.expect("a packet")?)?;
+            let second = parse_packet(p.expect("a second packet")?)?;
+            p.end()?;
+            Ok((first, second))
+        })
+        .collect()
+}
+
+pub fn input_generator_part2(input: &str) -> Result<Vec<Value>, ParseError> {
+    parse::lines(Day13::DAY, input)
+        .filter(|l| !l.is_empty())
+        .map(parse_packet)
+        .collect()
+}
+
+pub fn solve_part1(input: &[(Value, Value)]) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, (first, second))| first.cmp(second) == Ordering::Less)
+        .map(|(index, _)| index + 1)
+        .sum()
+}
+
+pub fn solve_part2(input: &[Value]) -> usize {
+    let divider_1 = Value::List(vec![Value::List(vec![Value::Integer(2)])]);
+    let divider_2 = Value::List(vec![Value::List(vec![Value::Integer(6)])]);
+    let mut input = input.to_owned();
+    // include those packets and sort
+    input.push(divider_1.clone());
+    input.push(divider_2.clone());
+    input.sort();
+    let index_1 = input
+        .iter()
+        .cloned()
+        .enumerate()
+        .find(|(_, d)| *d == divider_1)
+        .unwrap()
+        .0;
+    let index_2 = input
+        .iter()
+        .cloned()
+        .enumerate()
+        .find(|(_, d)| *d == divider_2)
+        .unwrap()
+        .0;
+    (index_1 + 1) * (index_2 + 1)
+}
+
+// `pairs` pairs of packets, nested up to four lists deep
+pub fn generate(rng: &mut Rng, pairs: usize) -> String {
+    fn packet(rng: &mut Rng, depth: usize) -> String {
+        let items = (0..rng.below(6))
+            .map(|_| {
+                if depth < 4 && rng.one_in(3) {
+                    packet(rng, depth + 1)
+                } else {
+                    rng.range(0..=10).to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", items.join(","))
+    }
+
+    (0..pairs)
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Part1Input = Vec<(Value, Value)>;
+    type Part2Input = Vec<Value>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part2(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day13);
@@ -0,0 +1,359 @@
+//! Day 14: Regolith Reservoir.
+//!
+//! Pours sand into a [`Cave`] until it falls out of the bottom, and then until it
+//! fills up to the source on top of the floor.
+
+use aoc_common::{parse, rng::Rng, NoParams, ParseError, Simulation, Solution};
+use itertools::Itertools;
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Vacant,
+    Rock,
+    Sand,
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Vacant => write!(f, "."),
+            Self::Rock => write!(f, "#"),
+            Self::Sand => write!(f, "o"),
+        }
+    }
+}
+
+pub type Coord = aoc_common::Coord<i32>;
+
+pub fn next_moves(coord: Coord) -> Vec<Coord> {
+    let new_y = coord.y + 1;
+    // first straight down, then left, then right
+    vec![
+        Coord {
+            x: coord.x,
+            y: new_y,
+        },
+        Coord {
+            x: coord.x - 1,
+            y: new_y,
+        },
+        Coord {
+            x: coord.x + 1,
+            y: new_y,
+        },
+    ]
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Move {
+    AtRest(Coord),
+    MovedTo(Coord),
+    FellOff,
+}
+
+pub struct Bounds {
+    left: Option<i32>,
+    right: Option<i32>,
+    bottom: Option<i32>,
+}
+
+impl Bounds {
+    pub fn unbounded() -> Self {
+        Self {
+            left: None,
+            right: None,
+            bottom: None,
+        }
+    }
+
+    pub fn within_bounds(&self, c: &Coord) -> bool {
+        self.left.map(|l| c.x >= l).unwrap_or(true)
+            && self.right.map(|r| c.x <= r).unwrap_or(true)
+            && self.bottom.map(|b| c.y <= b).unwrap_or(true)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cave {
+    map: HashMap<Coord, Tile>,
+    bottom: i32,
+    left: i32,
+    right: i32,
+}
+
+impl Cave {
+    // in part 2, it turns out that there's a secret floor
+    // 2 rows below the bottom -
+    // so hack the map to pretend it knows about this
+    // (it's infinitely wide, so I'd rather not insert it)
+    // technically this would mess up some bounding-boxes, but
+    // not the one we use here for part 1
+    pub fn get_with_floor(&self, coord: &Coord) -> Option<&Tile> {
+        if coord.y == self.bottom + 2 {
+            Some(&Tile::Rock)
+        } else {
+            self.map.get(coord)
+        }
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        Bounds {
+            left: Some(self.left),
+            right: Some(self.right),
+            bottom: Some(self.bottom),
+        }
+    }
+
+    pub fn drop_sand(&mut self, bounds: &Bounds) -> Option<()> {
+        let sand_start = Coord { x: 500, y: 0 };
+        if let Some(Tile::Sand) = self.map.get(&sand_start) {
+            // sand already fills the start point - we can't do anything
+            return None;
+        }
+
+        let mut last_movement = Move::MovedTo(sand_start);
+        while let Move::MovedTo(position) = last_movement {
+            let new_position = next_moves(position)
+                .into_iter()
+                .map(|c| (c, self.get_with_floor(&c).unwrap_or(&Tile::Vacant)))
+                .find(|(_, &t)| matches!(t, Tile::Vacant));
+            match new_position {
+                Some((c, _)) => {
+                    // sand wants to move to this coord
+                    if bounds.within_bounds(&c) {
+                        last_movement = Move::MovedTo(c)
+                    } else {
+                        // things that go out of bounds vanish
+                        last_movement = Move::FellOff
+                    }
+                }
+                None => last_movement = Move::AtRest(position),
+            }
+        }
+
+        match last_movement {
+            Move::FellOff => None,
+            Move::AtRest(position) => {
+                self.map.insert(position, Tile::Sand);
+                Some(())
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Display for Cave {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let min_y = 0;
+        // cave grows in part 2, so recalculate these here
+        let max_y = self.map.keys().map(|c| c.y).max().unwrap();
+        let min_x = self.map.keys().map(|c| c.x).min().unwrap();
+        let max_x = self.map.keys().map(|c| c.x).max().unwrap();
+        for row in min_y..=max_y {
+            for col in min_x..=max_x {
+                if row == 0 && col == 500 {
+                    write!(f, "+")?;
+                } else {
+                    let tile = self
+                        .map
+                        .get(&Coord { x: col, y: row })
+                        .unwrap_or(&Tile::Vacant);
+                    tile.fmt(f)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        writeln!(f)
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Cave, ParseError> {
+    let mut map = HashMap::new();
+    let mut lines = parse::lines(Day14::DAY, input);
+    for mut l in lines.by_ref() {
+        // each corner, and where it starts on the line
+        let mut coords = Vec::new();
+        loop {
+            let position = l.position();
+            let x = l.number()?;
+            l.tag(",")?;
+            let y = l.number()?;
+            coords.push((position, Coord { x, y }));
+            if !l.try_tag(" -> ") {
+                break;
+            }
+        }
+        l.end()?;
+
+        for ((_, first), (position, second)) in coords.into_iter().tuple_windows() {
+            if first.y == second.y {
+                // moving left or right
+                for x in min(first.x, second.x)..=max(first.x, second.x) {
+                    map.insert(Coord { x, y: first.y }, Tile::Rock);
+                }
+            } else if first.x == second.x {
+                // moving up or down
+                for y in min(first.y, second.y)..=max(first.y, second.y) {
+                    map.insert(Coord { x: first.x, y }, Tile::Rock);
+                }
+            } else {
+                // rock only goes in straight lines
+                return Err(l.error_at(position, "a point in line with the previous one"));
+            }
+        }
+    }
+
+    if map.is_empty() {
+        return Err(lines.end_error("a path of rock"));
+    }
+
+    // defines the portion of the cave that we scanned
+    let bottom = map.keys().map(|c| c.y).max().unwrap();
+    let left = map.keys().map(|c| c.x).min().unwrap();
+    let right = map.keys().map(|c| c.x).max().unwrap();
+
+    Ok(Cave {
+        map,
+        bottom,
+        left,
+        right,
+    })
+}
+
+// pours sand into the cave a grain at a time, until it either falls out of
+// the bounds or blocks the source
+pub struct Sandfall {
+    cave: Cave,
+    bounds: Bounds,
+    grains: usize,
+}
+
+impl Sandfall {
+    pub fn new(cave: &Cave, bounds: Bounds) -> Self {
+        Self {
+            cave: cave.clone(),
+            bounds,
+            grains: 0,
+        }
+    }
+
+    pub fn cave(&self) -> &Cave {
+        &self.cave
+    }
+}
+
+impl Simulation for Sandfall {
+    // drop one grain, and let it come to rest
+    fn step(&mut self) -> bool {
+        let landed = self.cave.drop_sand(&self.bounds).is_some();
+        if landed {
+            self.grains += 1;
+        }
+        landed
+    }
+
+    // the number of grains that have come to rest
+    fn steps(&self) -> usize {
+        self.grains
+    }
+
+    fn render(&self) -> String {
+        self.cave.to_string()
+    }
+}
+
+pub fn solve_part1(input: &Cave) -> usize {
+    let mut sand = Sandfall::new(input, input.bounds());
+    sand.run();
+    sand.steps()
+}
+
+pub fn solve_part2(input: &Cave) -> usize {
+    let mut sand = Sandfall::new(input, Bounds::unbounded());
+    sand.run();
+    sand.steps()
+}
+
+// `paths` paths of rock, scattered under the sand's source - the more paths,
+// the wider and deeper the cave
+pub fn generate(rng: &mut Rng, paths: usize) -> String {
+    let spread = 10 + paths as i64;
+    (0..paths)
+        .map(|_| {
+            let mut x = rng.range(500 - spread..=500 + spread);
+            let mut y = rng.range(5..=5 + spread);
+            let mut corners = vec![format!("{x},{y}")];
+            for corner in 0..rng.range(1..=4) {
+                let length = rng.range(1..=8);
+                if corner % 2 == 0 {
+                    x += if rng.one_in(2) { length } else { -length };
+                } else {
+                    y += length;
+                }
+                corners.push(format!("{x},{y}"));
+            }
+            corners.join(" -> ") + "\n"
+        })
+        .collect()
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Part1Input = Cave;
+    type Part2Input = Cave;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day14);
+
+#[test]
+fn test_sandfall_render() {
+    let input =
+        input_generator_part1("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n")
+            .unwrap();
+    let mut sand = Sandfall::new(&input, input.bounds());
+    assert!(sand.run_for(5));
+    assert_eq!(
+        sand.render(),
+        "......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+......o.#.
+....oooo#.
+#########.
+
+"
+    );
+}
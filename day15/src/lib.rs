@@ -0,0 +1,385 @@
+//! Day 15: Beacon Exclusion Zone.
+//!
+//! Works out where beacons can't be from each [`SensorReport`], along one row in
+//! part 1, and finds the one place the distress beacon can be in part 2.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    ParseError, Solution,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+pub type Coord = aoc_common::Coord<i32>;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Tile {
+    Sensor,
+    Beacon,
+    Unknown,
+}
+
+#[derive(Clone)]
+pub struct TunnelNetwork {
+    map: HashMap<Coord, Tile>,
+}
+
+impl TunnelNetwork {
+    pub fn add_sensor(&mut self, sensor_report: &SensorReport) {
+        self.map.insert(sensor_report.sensor_location, Tile::Sensor);
+        self.map.insert(sensor_report.nearest_beacon, Tile::Beacon);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SensorReport {
+    sensor_location: Coord,
+    nearest_beacon: Coord,
+}
+
+impl SensorReport {
+    pub fn distance_scanned(&self) -> i32 {
+        self.sensor_location
+            .manhattan_distance_to(&self.nearest_beacon)
+    }
+
+    fn x_range_scanned(&self) -> (i32, i32) {
+        let d = self.distance_scanned();
+        (self.sensor_location.x - d, self.sensor_location.x + d)
+    }
+
+    pub fn y_range_scanned_at(&self, x: i32) -> Option<(i32, i32)> {
+        let d = self.distance_scanned();
+        let dx = (x - self.sensor_location.x).abs();
+        if dx > d {
+            return None;
+        }
+        let dy = d - dx;
+        Some((self.sensor_location.y - dy, self.sensor_location.y + dy))
+    }
+}
+
+pub struct Input {
+    tunnels: TunnelNetwork,
+    sensors: Vec<SensorReport>,
+}
+
+// "x=2, y=18"
+fn parse_coord(line: &mut Line) -> Result<Coord, ParseError> {
+    line.tag("x=")?;
+    let x = line.number()?;
+    line.tag(", y=")?;
+    let y = line.number()?;
+    Ok(Coord { x, y })
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Input, ParseError> {
+    let mut sensors = Vec::new();
+    for mut line in parse::lines(Day15::DAY, input) {
+        line.tag("Sensor at ")?;
+        let sensor_location = parse_coord(&mut line)?;
+        line.tag(": closest beacon is at ")?;
+        let nearest_beacon = parse_coord(&mut line)?;
+        line.end()?;
+        sensors.push(SensorReport {
+            sensor_location,
+            nearest_beacon,
+        });
+    }
+
+    let mut tunnels = TunnelNetwork {
+        map: HashMap::new(),
+    };
+    for sensor in sensors.clone() {
+        tunnels.add_sensor(&sensor);
+    }
+
+    Ok(Input { tunnels, sensors })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    // the row to count the beacon-free positions in for part 1
+    pub row: i32,
+    // the distress beacon has x and y between 0 and this
+    pub search_limit: i32,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            row: 2_000_000,
+            search_limit: 4_000_000,
+        }
+    }
+}
+
+pub fn solve_part1(input: &Input, params: &Params) -> usize {
+    let min_x = input
+        .sensors
+        .iter()
+        .map(|s| s.x_range_scanned().0)
+        .min()
+        .unwrap();
+    let max_x = input
+        .sensors
+        .iter()
+        .map(|s| s.x_range_scanned().1)
+        .max()
+        .unwrap();
+    let row = params.row;
+
+    let mut row_cells = HashSet::new();
+    for x in min_x..=max_x {
+        let c = (x, row).into();
+        if matches!(
+            input.tunnels.map.get(&c).unwrap_or(&Tile::Unknown),
+            Tile::Sensor | Tile::Beacon
+        ) {
+            continue;
+        }
+        if input
+            .sensors
+            .iter()
+            .any(|s| s.sensor_location.manhattan_distance_to(&c) <= s.distance_scanned())
+        {
+            row_cells.insert(c);
+        }
+    }
+
+    row_cells.len()
+}
+
+// the distress beacon's tuning frequency - if the sensors leave anywhere in the
+// search area for it to be. the real puzzle always does, but a smaller
+// `search_limit` might not
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TuningFrequency(pub Option<u128>);
+
+impl Display for TuningFrequency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(frequency) => write!(f, "{frequency}"),
+            None => write!(f, "no position found"),
+        }
+    }
+}
+
+pub fn solve_part2(input: &Input, params: &Params) -> TuningFrequency {
+    let limit = params.search_limit;
+    for x in 0..=limit {
+        // get all the ranges and order them by lower bound
+        let mut ranges = input
+            .sensors
+            .iter()
+            .filter_map(|s| s.y_range_scanned_at(x))
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|(min, _)| *min);
+        let mut y = 0;
+        for (min, max) in ranges {
+            if y > limit {
+                break;
+            }
+            if y < min {
+                // next range starts after where we currently are - so beacon must be this point
+                break;
+            }
+            // inside (or past) a range, so can't be anything in this range - skip past it
+            y = y.max(max + 1);
+        }
+        // either there was a gap, or the ranges ran out before the edge
+        if y <= limit {
+            return TuningFrequency(Some((x as u128) * (4_000_000_u128) + (y as u128)));
+        }
+    }
+
+    TuningFrequency(None)
+}
+
+// `sensors` sensors spread over the area the distress beacon can be in, each
+// seeing a long way
+pub fn generate(rng: &mut Rng, sensors: usize) -> String {
+    (0..sensors)
+        .map(|_| {
+            let (x, y) = (rng.range(0..=4_000_000), rng.range(0..=4_000_000));
+            let distance = rng.range(200_000..=1_000_000);
+            let dx = rng.range(-distance..=distance);
+            let dy = (distance - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+            format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}\n",
+                x + dx,
+                y + dy
+            )
+        })
+        .collect()
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Part1Input = Input;
+    type Part2Input = Input;
+    type Part1Output = usize;
+    type Part2Output = TuningFrequency;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input, params)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input, params)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day15);
+
+#[test]
+fn test_day15_bad_sensor() {
+    let input = "Sensor at x=2, y=18: closest beacon is at x=-2, z=15\n";
+
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.line, error.column), (1, 47));
+    assert_eq!(error.expected, "`, y=`");
+}
+
+#[test]
+fn test_day15_nowhere_left() {
+    // the one sensor can see the whole of the (tiny) search area
+    let input = input_generator_part1(
+        "Sensor at x=0, y=0: closest beacon is at x=2, y=0
+",
+    )
+    .unwrap();
+    let params = Params {
+        search_limit: 1,
+        ..Params::default()
+    };
+    let frequency = solve_part2(&input, &params);
+    assert_eq!(frequency, TuningFrequency(None));
+    assert_eq!(frequency.to_string(), "no position found");
+}
+
+// part 2 skips along each column a sensor's range at a time - check that against
+// looking at every position in a small search area
+#[cfg(test)]
+mod differential {
+    use super::*;
+    use aoc_common::{
+        differential::{self, Differential},
+        rng::Rng,
+    };
+
+    struct RangeSkipping;
+
+    #[derive(Clone, Debug)]
+    struct Area {
+        sensors: Vec<SensorReport>,
+        search_limit: i32,
+    }
+
+    impl Differential for RangeSkipping {
+        type Input = Area;
+        type Output = TuningFrequency;
+
+        // none of the sensors can reach the distress beacon, so there's always
+        // somewhere for it to be
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let limit = rng.range(1..=20);
+            let distress: Coord = (rng.range(0..=limit) as i32, rng.range(0..=limit) as i32).into();
+            let mut sensors = Vec::new();
+            for _ in 0..rng.below(10) {
+                let sensor_location: Coord = (
+                    rng.range(-5..=limit + 5) as i32,
+                    rng.range(-5..=limit + 5) as i32,
+                )
+                    .into();
+                let reach = sensor_location.manhattan_distance_to(&distress) as i64 - 1;
+                if reach < 0 {
+                    continue;
+                }
+                let distance = rng.range(0..=reach);
+                let dx = rng.range(-distance..=distance);
+                let dy = (distance - dx.abs()) * if rng.one_in(2) { 1 } else { -1 };
+                sensors.push(SensorReport {
+                    sensor_location,
+                    nearest_beacon: sensor_location + (dx as i32, dy as i32),
+                });
+            }
+            Area {
+                sensors,
+                search_limit: limit as i32,
+            }
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            differential::shrink_vec(&input.sensors, 0)
+                .into_iter()
+                .map(|sensors| Area {
+                    sensors,
+                    ..input.clone()
+                })
+                .collect()
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            for x in 0..=input.search_limit {
+                for y in 0..=input.search_limit {
+                    let c: Coord = (x, y).into();
+                    if input
+                        .sensors
+                        .iter()
+                        .all(|s| s.sensor_location.manhattan_distance_to(&c) > s.distance_scanned())
+                    {
+                        return TuningFrequency(Some((x as u128) * 4_000_000 + (y as u128)));
+                    }
+                }
+            }
+            TuningFrequency(None)
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            let text = input
+                .sensors
+                .iter()
+                .map(|s| {
+                    format!(
+                        "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                        s.sensor_location.x,
+                        s.sensor_location.y,
+                        s.nearest_beacon.x,
+                        s.nearest_beacon.y
+                    )
+                })
+                .collect::<String>();
+            let params = Params {
+                search_limit: input.search_limit,
+                ..Params::default()
+            };
+            solve_part2(&input_generator_part1(&text).unwrap(), &params)
+        }
+    }
+
+    #[test]
+    fn test_range_skipping() {
+        differential::check::<RangeSkipping>(300);
+    }
+}
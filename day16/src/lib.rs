@@ -0,0 +1,680 @@
+//! Day 16: Proboscidea Volcanium.
+//!
+//! Searches for the most pressure that can be released by opening [`Valve`]s,
+//! alone in part 1 and with an elephant's help in part 2.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    search, stats, ParseError, Solution,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub struct Input {}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash, PartialOrd, Ord)]
+pub struct ValveId {
+    id: String,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FlowRate {
+    Starting,
+    Broken,
+    Flow(usize),
+}
+
+pub struct Valve {
+    flow_rate: FlowRate,
+    subsequent_valves: HashSet<ValveId>,
+}
+
+#[derive(Debug)]
+pub struct DistancedValve {
+    flow_rate: FlowRate,
+    distanced_valves: HashSet<(ValveId, usize)>,
+}
+
+// every tunnel takes a minute, so a breadth-first search finds the shortest
+// time from start to every other valve
+pub fn calculate_distances(
+    tunnels: &HashMap<ValveId, Valve>,
+    start: &ValveId,
+) -> HashMap<ValveId, usize> {
+    search::bfs(start.clone(), |current_node| {
+        tunnels
+            .get(current_node)
+            .unwrap()
+            .subsequent_valves
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+    })
+    .distances
+}
+
+fn parse_valve_id(line: &mut Line) -> Result<ValveId, ParseError> {
+    let id = line.take_while1("a valve name", |c| c.is_ascii_uppercase())?;
+    Ok(ValveId { id: id.to_owned() })
+}
+
+pub fn input_generator_part1(input: &str) -> Result<HashMap<ValveId, DistancedValve>, ParseError> {
+    let mut all_valves = HashMap::new();
+    // tunnels to valves that we need to check actually exist, once we've seen them all
+    let mut tunnel_ends = Vec::new();
+
+    let mut lines = parse::lines(Day16::DAY, input);
+    for mut line in lines.by_ref() {
+        line.tag("Valve ")?;
+        let valve_id = parse_valve_id(&mut line)?;
+        line.tag(" has flow rate=")?;
+        let flow = line.number()?;
+        line.tag("; ")?;
+        let flow_rate = match flow {
+            0 if valve_id.id == "AA" => FlowRate::Starting,
+            0 => FlowRate::Broken,
+            i => FlowRate::Flow(i),
+        };
+        let many = line.one_of(&[
+            ("tunnels lead to valves ", true),
+            ("tunnel leads to valve ", false),
+        ])?;
+        let mut subsequent_valves = HashSet::new();
+        loop {
+            let position = line.position();
+            let subsequent_valve = parse_valve_id(&mut line)?;
+            tunnel_ends.push((
+                subsequent_valve.clone(),
+                line.error_at(position, "a valve that's in the scan"),
+            ));
+            subsequent_valves.insert(subsequent_valve);
+            if !(many && line.try_tag(", ")) {
+                break;
+            }
+        }
+        line.end()?;
+
+        all_valves.insert(
+            valve_id,
+            Valve {
+                flow_rate,
+                subsequent_valves,
+            },
+        );
+    }
+
+    if let Some((_, error)) = tunnel_ends
+        .into_iter()
+        .find(|(valve_id, _)| !all_valves.contains_key(valve_id))
+    {
+        return Err(error);
+    }
+    if !all_valves.contains_key(&ValveId {
+        id: "AA".to_owned(),
+    }) {
+        return Err(lines.end_error("a valve named `AA` to start from"));
+    }
+
+    let mut all_distanced_valves = HashMap::new();
+
+    // there's no point remembering the valves with flow rate 0 - it will take too long to
+    // explore all the paths.
+    for (valve_id, valve) in all_valves.iter() {
+        // only check ones that are worth remembering
+        if matches!(valve.flow_rate, FlowRate::Broken) {
+            continue;
+        }
+        let distances = calculate_distances(&all_valves, valve_id);
+        let mut distanced_valves = HashSet::new();
+        for (target_valve, d) in distances.into_iter() {
+            let target_flow_rate = all_valves.get(&target_valve).unwrap().flow_rate;
+            if d > 0 && !matches!(target_flow_rate, FlowRate::Broken | FlowRate::Starting) {
+                distanced_valves.insert((target_valve, d));
+            }
+        }
+        all_distanced_valves.insert(
+            valve_id.clone(),
+            DistancedValve {
+                flow_rate: valve.flow_rate,
+                distanced_valves,
+            },
+        );
+    }
+
+    Ok(all_distanced_valves)
+}
+
+pub fn find_max_pressure(
+    tunnels: &HashMap<ValveId, DistancedValve>,
+    already_on_valves: HashSet<ValveId>,
+    starting_from: &ValveId,
+    time_remaining: usize,
+    // flow rate of all open valves so far - use this when travelling
+    current_flow_rate: usize,
+) -> usize {
+    // assume current location is already 'on'
+    // (not technically true for the start, but there's no point turning it on)
+    let mut max_pressure = 0;
+    let current_location = tunnels.get(starting_from).unwrap();
+    stats::count("nodes expanded");
+
+    let valves_worth_considering = current_location
+        .distanced_valves
+        .iter()
+        .filter(|&(destination, distance)| {
+            if already_on_valves.contains(destination) {
+                stats::count("pruned: valve already open");
+                return false;
+            }
+            if distance + 1 > time_remaining {
+                stats::count("pruned: too far to open in time");
+                return false;
+            }
+            true
+        })
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    if valves_worth_considering.is_empty() {
+        return current_flow_rate * time_remaining;
+    }
+
+    for (destination, distance) in valves_worth_considering {
+        // otherwise: go there, turn on the valve, and see how that does
+        // we're only going there to turn on the valve, so include the minute that takes
+        let pressure_added_while_travelling = current_flow_rate * (distance + 1);
+        let time_left = time_remaining - distance - 1;
+        let mut valves_now_on = already_on_valves.clone();
+        valves_now_on.insert(destination.clone());
+        let destination_flow_rate = tunnels.get(&destination).unwrap().flow_rate;
+        let new_flow_rate = current_flow_rate
+            + match destination_flow_rate {
+                FlowRate::Flow(x) => x,
+                _ => unreachable!(),
+            };
+        let max_pressure_added_via_this_route = pressure_added_while_travelling
+            + find_max_pressure(
+                tunnels,
+                valves_now_on,
+                &destination,
+                time_left,
+                new_flow_rate,
+            );
+
+        max_pressure = max_pressure.max(max_pressure_added_via_this_route);
+    }
+
+    max_pressure
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    At(ValveId),
+    // number of steps left to get there
+    TravellingTo(ValveId, usize),
+    // Done(usize),
+}
+
+pub fn distance_to(
+    tunnels: &HashMap<ValveId, DistancedValve>,
+    from: &ValveId,
+    to: &ValveId,
+) -> usize {
+    tunnels
+        .get(from)
+        .unwrap()
+        .distanced_valves
+        .iter()
+        .find(|(v, _)| v == to)
+        .unwrap()
+        .1
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    // how long there is before the volcano erupts
+    pub minutes_part1: usize,
+    // and how long's left after teaching the elephant
+    pub minutes_part2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            minutes_part1: 30,
+            minutes_part2: 26,
+        }
+    }
+}
+
+pub fn solve_part1(input: &HashMap<ValveId, DistancedValve>, params: &Params) -> usize {
+    let start = ValveId {
+        id: "AA".to_owned(),
+    };
+    find_max_pressure(
+        input,
+        HashSet::from([start.clone()]),
+        &start,
+        params.minutes_part1,
+        0,
+    )
+}
+
+pub fn number_to_subset(valves_by_id: &[ValveId], number: usize) -> HashSet<ValveId> {
+    let mut valves = HashSet::new();
+    for (index, valve_id) in valves_by_id.iter().enumerate() {
+        let bitmask = 1 << index;
+        if number & bitmask != 0 {
+            valves.insert(valve_id.clone());
+        }
+    }
+    valves
+}
+
+pub fn number_to_complementary_number(total_valves: usize, number: usize) -> usize {
+    let mask = (1 << total_valves) - 1;
+    let inverse = !number;
+    inverse & mask
+}
+
+pub fn solve_part2(input: &HashMap<ValveId, DistancedValve>, params: &Params) -> usize {
+    let start = ValveId {
+        id: "AA".to_owned(),
+    };
+
+    let mut valves_to_turn = input
+        .keys()
+        .filter(|&v| v.id != "AA")
+        .cloned()
+        .collect::<Vec<_>>();
+    valves_to_turn.sort();
+    let valves_to_turn = valves_to_turn;
+    let mut partitions_checked = HashSet::new();
+    let mut max_pressure = 0;
+    // (the partitions come in pairs, so it's only half of them - unless there's
+    // nothing worth opening at all)
+    for i in 0..(2_usize.pow(valves_to_turn.len().saturating_sub(1) as u32)) {
+        let mut valves_for_me = number_to_subset(&valves_to_turn, i);
+        let complement = number_to_complementary_number(valves_to_turn.len(), i);
+        let mut valves_for_elephant = number_to_subset(&valves_to_turn, complement);
+
+        let my_valves = valves_for_me.clone();
+        assert_eq!(
+            my_valves
+                .union(&valves_for_elephant)
+                .collect::<HashSet<_>>(),
+            valves_to_turn.iter().collect::<HashSet<_>>()
+        );
+
+        valves_for_me.insert(start.clone());
+        valves_for_elephant.insert(start.clone());
+        stats::count("partitions searched");
+
+        let my_max_pressure_this_partition = find_max_pressure(
+            input,
+            valves_for_elephant.clone(),
+            &start,
+            params.minutes_part2,
+            0,
+        );
+        let elephant_max_pressure_this_partition = find_max_pressure(
+            input,
+            valves_for_me.clone(),
+            &start,
+            params.minutes_part2,
+            0,
+        );
+        max_pressure =
+            max_pressure.max(my_max_pressure_this_partition + elephant_max_pressure_this_partition);
+
+        partitions_checked.insert(i);
+        partitions_checked.insert(complement);
+    }
+
+    max_pressure
+}
+
+// `valves` valves (between 2 and 676, as that's all the names there are), all
+// reachable from AA. about a third of them have any flow
+pub fn generate(rng: &mut Rng, valves: usize) -> String {
+    let valves = valves.clamp(2, 26 * 26);
+    let mut names = (1..26 * 26)
+        .map(|i| {
+            format!(
+                "{}{}",
+                (b'A' + (i / 26) as u8) as char,
+                (b'A' + (i % 26) as u8) as char
+            )
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    names.insert(0, "AA".to_string());
+
+    // a tree joining them all up, and then a few shortcuts
+    let mut tunnels = vec![Vec::new(); valves];
+    let mut join = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..valves {
+        join(valve, rng.below(valve));
+    }
+    for _ in 0..valves / 4 {
+        join(rng.below(valves), rng.below(valves));
+    }
+
+    (0..valves)
+        .map(|valve| {
+            let flow = if valve > 0 && rng.one_in(3) {
+                rng.range(1..=25)
+            } else {
+                0
+            };
+            let leads_to = tunnels[valve]
+                .iter()
+                .map(|&t| names[t].as_str())
+                .collect::<Vec<_>>();
+            let tunnels = match leads_to.len() {
+                1 => "tunnel leads to valve",
+                _ => "tunnels lead to valves",
+            };
+            format!(
+                "Valve {} has flow rate={flow}; {tunnels} {}\n",
+                names[valve],
+                leads_to.join(", ")
+            )
+        })
+        .collect()
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Part1Input = HashMap<ValveId, DistancedValve>;
+    type Part2Input = HashMap<ValveId, DistancedValve>;
+    type Part1Output = usize;
+    type Part2Output = usize;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input, params)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input, params)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day16);
+
+#[test]
+fn test_bitmasking() {
+    let number = 0b101;
+    let number_of_valves = 5;
+    assert_eq!(
+        0b11010,
+        number_to_complementary_number(number_of_valves, number)
+    );
+}
+
+#[test]
+fn test_day16_unknown_valve() {
+    let input = r#"Valve AA has flow rate=0; tunnels lead to valves BB, ZZ
+Valve BB has flow rate=13; tunnel leads to valve AA
+"#;
+
+    let error = input_generator_part1(input).err().unwrap();
+    assert_eq!((error.line, error.column), (1, 54));
+    assert_eq!(error.found, "`ZZ`");
+}
+
+// the solvers only travel between the valves worth opening, and part 2 splits
+// those between me and the elephant - check both against a search that goes
+// minute by minute through the tunnels, with the two of us moving together
+#[cfg(test)]
+mod differential {
+    use super::*;
+    use aoc_common::{
+        differential::{self, Differential},
+        rng::Rng,
+    };
+
+    struct ValveSearch;
+
+    // valve 0 is AA, and the tunnels go both ways
+    #[derive(Clone, Debug)]
+    struct Scan {
+        flows: Vec<usize>,
+        tunnels: Vec<Vec<usize>>,
+        minutes: usize,
+    }
+
+    impl Scan {
+        fn name(valve: usize) -> String {
+            let letter = (b'A' + valve as u8) as char;
+            format!("{letter}{letter}")
+        }
+
+        fn to_input(&self) -> String {
+            let mut text = String::new();
+            for (valve, (flow, tunnels)) in self.flows.iter().zip(&self.tunnels).enumerate() {
+                let names = tunnels.iter().map(|&t| Self::name(t)).collect::<Vec<_>>();
+                let leads = if names.len() == 1 {
+                    "tunnel leads to valve"
+                } else {
+                    "tunnels lead to valves"
+                };
+                text += &format!(
+                    "Valve {} has flow rate={flow}; {leads} {}\n",
+                    Self::name(valve),
+                    names.join(", ")
+                );
+            }
+            text
+        }
+
+        // every valve needs a tunnel out of it to be in a scan
+        fn is_valid(&self) -> bool {
+            self.flows.len() >= 2 && self.tunnels.iter().all(|t| !t.is_empty())
+        }
+
+        fn without_valve(&self, valve: usize) -> Self {
+            let renumber = |t: usize| if t > valve { t - 1 } else { t };
+            let mut flows = self.flows.clone();
+            flows.remove(valve);
+            let mut tunnels = self.tunnels.clone();
+            tunnels.remove(valve);
+            for ends in &mut tunnels {
+                ends.retain(|&t| t != valve);
+                ends.iter_mut().for_each(|t| *t = renumber(*t));
+            }
+            Self {
+                flows,
+                tunnels,
+                ..self.clone()
+            }
+        }
+
+        // the most pressure that can be released from here, going a minute at a time
+        fn best_alone(
+            &self,
+            at: usize,
+            open: u32,
+            minutes: usize,
+            memo: &mut HashMap<(usize, u32, usize), usize>,
+        ) -> usize {
+            if minutes == 0 {
+                return 0;
+            }
+            if let Some(&best) = memo.get(&(at, open, minutes)) {
+                return best;
+            }
+            let mut best = 0;
+            if self.flows[at] > 0 && open & (1 << at) == 0 {
+                best = self.flows[at] * (minutes - 1)
+                    + self.best_alone(at, open | (1 << at), minutes - 1, memo);
+            }
+            for &next in &self.tunnels[at] {
+                best = best.max(self.best_alone(next, open, minutes - 1, memo));
+            }
+            memo.insert((at, open, minutes), best);
+            best
+        }
+
+        // and with the elephant, each of us either opening a valve or moving on every minute
+        fn best_together(
+            &self,
+            (me, elephant): (usize, usize),
+            open: u32,
+            minutes: usize,
+            memo: &mut HashMap<((usize, usize), u32, usize), usize>,
+        ) -> usize {
+            if minutes == 0 {
+                return 0;
+            }
+            // it doesn't matter which of us is where
+            let at = (me.min(elephant), me.max(elephant));
+            if let Some(&best) = memo.get(&(at, open, minutes)) {
+                return best;
+            }
+
+            // what each of us could do: Err to open the valve here, or Ok to move on
+            let options = |valve: usize| {
+                let mut options = self.tunnels[valve]
+                    .iter()
+                    .map(|&t| Ok(t))
+                    .collect::<Vec<_>>();
+                if self.flows[valve] > 0 && open & (1 << valve) == 0 {
+                    options.push(Err(valve));
+                }
+                options
+            };
+            let mut best = 0;
+            for mine in options(at.0) {
+                for theirs in options(at.1) {
+                    if mine.is_err() && mine == theirs {
+                        continue;
+                    }
+                    let mut released = 0;
+                    let mut now_open = open;
+                    let mut moved_to = |action: Result<usize, usize>| match action {
+                        Ok(next) => next,
+                        Err(valve) => {
+                            released += self.flows[valve] * (minutes - 1);
+                            now_open |= 1 << valve;
+                            valve
+                        }
+                    };
+                    let next = (moved_to(mine), moved_to(theirs));
+                    best =
+                        best.max(released + self.best_together(next, now_open, minutes - 1, memo));
+                }
+            }
+            memo.insert((at, open, minutes), best);
+            best
+        }
+    }
+
+    impl Differential for ValveSearch {
+        type Input = Scan;
+        type Output = (usize, usize);
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let size = rng.range(2..=7) as usize;
+            let mut tunnels = vec![Vec::new(); size];
+            let mut connect = |a: usize, b: usize| {
+                if a != b && !tunnels[a].contains(&b) {
+                    tunnels[a].push(b);
+                    tunnels[b].push(a);
+                }
+            };
+            // everything's reachable from somewhere earlier, and then some shortcuts
+            for valve in 1..size {
+                connect(valve, rng.below(valve));
+            }
+            for _ in 0..rng.below(size) {
+                connect(rng.below(size), rng.below(size));
+            }
+
+            let flows = (0..size)
+                .map(|valve| match valve {
+                    0 => 0,
+                    _ if rng.one_in(3) => 0,
+                    _ => rng.range(1..=25) as usize,
+                })
+                .collect();
+            Scan {
+                flows,
+                tunnels,
+                minutes: rng.range(1..=12) as usize,
+            }
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            let mut smaller = Vec::new();
+            for minutes in differential::shrink_number(input.minutes as i64)
+                .into_iter()
+                .filter(|&m| m >= 1)
+            {
+                smaller.push(Scan {
+                    minutes: minutes as usize,
+                    ..input.clone()
+                });
+            }
+            for valve in 1..input.flows.len() {
+                smaller.push(input.without_valve(valve));
+                if input.flows[valve] > 0 {
+                    let mut flows = input.flows.clone();
+                    flows[valve] = 0;
+                    smaller.push(Scan {
+                        flows,
+                        ..input.clone()
+                    });
+                }
+            }
+            smaller.retain(|scan| scan.is_valid());
+            smaller
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            (
+                input.best_alone(0, 0, input.minutes, &mut HashMap::new()),
+                input.best_together((0, 0), 0, input.minutes, &mut HashMap::new()),
+            )
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            let tunnels = input_generator_part1(&input.to_input()).unwrap();
+            let params = Params {
+                minutes_part1: input.minutes,
+                minutes_part2: input.minutes,
+            };
+            (
+                solve_part1(&tunnels, &params),
+                solve_part2(&tunnels, &params),
+            )
+        }
+    }
+
+    #[test]
+    fn test_valve_search() {
+        differential::check::<ValveSearch>(300);
+    }
+}
@@ -0,0 +1,555 @@
+//! Day 17: Pyroclastic Flow.
+//!
+//! Drops [`Rock`]s into the chamber, pushed about by the jets, and measures the
+//! height of the tower - directly for 2022 rocks, and by finding a cycle for a
+//! trillion.
+
+use aoc_common::{cycle, parse, rng::Rng, ParseError, Simulation, Solution};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+pub struct Input {}
+
+// |..@@@@.|
+// |.......|
+// |.......|
+// |.......| y == 0
+// +-------+
+//  x = 0  x = 7
+
+pub type Coord = aoc_common::Coord<i64>;
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Rock {
+    Bar,
+    Plus,
+    Corner,
+    Pipe,
+    Box,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Down,
+}
+
+impl Direction {
+    pub fn coord_delta(&self) -> (i64, i64) {
+        match self {
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, -1),
+        }
+    }
+}
+
+impl Rock {
+    pub fn sequence() -> Vec<Rock> {
+        vec![Rock::Bar, Rock::Plus, Rock::Corner, Rock::Pipe, Rock::Box]
+    }
+
+    // coordinate deltas relative to start position (in piece bottom-left)
+    pub fn relative_coords(&self, start: Coord) -> HashSet<Coord> {
+        match self {
+            Rock::Bar => HashSet::from([(0, 0), (1, 0), (2, 0), (3, 0)].map(|c| start + c)),
+            Rock::Pipe => HashSet::from([(0, 0), (0, 1), (0, 2), (0, 3)].map(|c| start + c)),
+            Rock::Box => HashSet::from([(0, 0), (0, 1), (1, 0), (1, 1)].map(|c| start + c)),
+            Rock::Corner => {
+                HashSet::from([(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)].map(|c| start + c))
+            }
+            Rock::Plus => {
+                HashSet::from([(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)].map(|c| start + c))
+            }
+        }
+    }
+
+    // the new coords a rock would fill, if it moved this way, which it isn't currently filling
+    pub fn relative_movefront_coords(&self, start: Coord, direction: &Direction) -> HashSet<Coord> {
+        let delta = direction.coord_delta();
+        let rock_coords = self.relative_coords(start);
+        let new_coords = rock_coords
+            .clone()
+            .into_iter()
+            .map(|c| c + delta)
+            .collect::<HashSet<_>>();
+        new_coords
+            .difference(&rock_coords)
+            .cloned()
+            .collect::<HashSet<_>>()
+    }
+}
+
+pub enum MoveResult {
+    RockLanded,
+    Boring,
+}
+
+// a well is: the coords above the 'baseline' (lowest settled rock in any column),
+// plus the offset into the gas jet sequence we saw it at, plus the rock we just placed
+#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+pub struct State {
+    well: Vec<Coord>,
+    jet_number: usize,
+    rock: Rock,
+}
+
+// how far below the top of the tower the well can go
+const MAX_WELL_DEPTH: i64 = 50;
+
+pub struct Game {
+    // "current rock location" is always its bounding box's bottom left
+    current_rock: (Rock, Coord),
+    current_pit: HashSet<Coord>,
+    number_of_rocks: usize,
+}
+
+impl Game {
+    pub fn with_starting_rock(start: Rock) -> Self {
+        Self {
+            current_rock: (start, (2, 3).into()),
+            current_pit: HashSet::new(),
+            number_of_rocks: 1,
+        }
+    }
+
+    pub fn baseline(&self) -> i64 {
+        let lowest_column = (0..7)
+            .map(|x| {
+                self.current_pit
+                    .iter()
+                    .filter(|c| c.x == x)
+                    .map(|c| c.y)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .min()
+            .unwrap_or(0);
+        // a column the jets never push anything into would make the well deeper
+        // with every rock, and it'd never repeat - so don't look further down
+        // than any rock could usefully fall
+        lowest_column.max(self.current_height() - MAX_WELL_DEPTH)
+    }
+
+    pub fn current_well(&self) -> Vec<Coord> {
+        let baseline = self.baseline();
+        let mut rocks_above_baseline = self
+            .current_pit
+            .iter()
+            .filter(|c| c.y >= baseline)
+            .map(|c| *c + (0, -baseline))
+            .collect::<Vec<_>>();
+        rocks_above_baseline.sort();
+        rocks_above_baseline
+    }
+
+    pub fn make_move(&mut self, direction: &Direction) -> MoveResult {
+        let places_rock_wants_to_move_to = self
+            .current_rock
+            .0
+            .relative_movefront_coords(self.current_rock.1, direction);
+        if places_rock_wants_to_move_to
+            .iter()
+            .any(|c| c.x < 0 || c.x >= 7 || c.y < 0 || self.current_pit.contains(c))
+        {
+            // can't move - there's something in the way
+            if matches!(direction, Direction::Down) {
+                // tried to move down, but can't - we've landed
+                self.current_pit
+                    .extend(&self.current_rock.0.relative_coords(self.current_rock.1));
+
+                if self.number_of_rocks.is_multiple_of(100) {
+                    // println!("Another hundred rocks placed, new baseline is {}", self.baseline());
+                }
+                // no need to do anything with the rock coords - it's already been merged with the pit
+                return MoveResult::RockLanded;
+            }
+
+            return MoveResult::Boring;
+        }
+
+        //otherwise we moved successfully
+        self.current_rock.1 = self.current_rock.1 + direction.coord_delta();
+        MoveResult::Boring
+    }
+
+    pub fn current_height(&self) -> i64 {
+        self.current_pit.iter().map(|c| c.y).max().unwrap_or(0)
+    }
+
+    pub fn spawn_new_rock(&mut self, rock: Rock) {
+        let height = self.current_height();
+        self.current_rock = (rock, (2, height + 4).into());
+        self.number_of_rocks += 1;
+    }
+}
+
+impl std::fmt::Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let current_rock_coords = self.current_rock.0.relative_coords(self.current_rock.1);
+        let mut y = self.current_height() + 7;
+        loop {
+            y -= 1;
+
+            if y == -1 {
+                writeln!(f, "+-------+")?;
+                break;
+            }
+
+            write!(f, "|")?;
+            for x in 0..7 {
+                let coord = (x, y).into();
+                if current_rock_coords.contains(&coord) {
+                    write!(f, "@")?;
+                } else if self.current_pit.contains(&coord) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+
+            writeln!(f, "|")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Direction>, ParseError> {
+    let mut lines = parse::lines(Day17::DAY, input);
+    let mut line = lines.expect("a jet pattern")?;
+    let mut directions = Vec::new();
+    // an empty pattern would never move any rocks sideways, or even end
+    loop {
+        directions.push(line.one_of(&[("<", Direction::Left), (">", Direction::Right)])?);
+        if line.is_empty() {
+            break;
+        }
+    }
+    lines.end()?;
+
+    Ok(directions)
+}
+
+// how many rocks to drop before measuring the tower
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    pub rocks_part1: usize,
+    pub rocks_part2: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            rocks_part1: 2022,
+            rocks_part2: 1_000_000_000_000,
+        }
+    }
+}
+
+// plays the game a move at a time: each jet of gas pushes the rock sideways,
+// and then it falls one. a new rock appears once the last one has landed
+pub struct Chamber<'a> {
+    game: Game,
+    jets: &'a [Direction],
+    moves: usize,
+    // the last move landed a rock - the next one's spawned on the next step
+    landed: bool,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [Direction]) -> Self {
+        Self {
+            game: Game::with_starting_rock(Rock::sequence()[0]),
+            jets,
+            moves: 0,
+            landed: false,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    // how many rocks have come to rest so far
+    pub fn rocks_landed(&self) -> usize {
+        if self.landed {
+            self.game.number_of_rocks
+        } else {
+            self.game.number_of_rocks - 1
+        }
+    }
+
+    // the rock that's just landed, if the last move landed one
+    pub fn just_landed(&self) -> Option<usize> {
+        self.landed.then_some(self.game.number_of_rocks)
+    }
+
+    pub fn tower_height(&self) -> i64 {
+        self.game.current_height() + 1
+    }
+
+    // the top of the tower, along with where we are in the jets and rocks -
+    // once this repeats, so does everything after it
+    pub fn state(&self) -> State {
+        State {
+            well: self.game.current_well(),
+            // the jet before the last move
+            jet_number: (self.moves.saturating_sub(1) / 2) % self.jets.len(),
+            rock: self.game.current_rock.0,
+        }
+    }
+}
+
+impl Simulation for Chamber<'_> {
+    fn step(&mut self) -> bool {
+        if self.landed {
+            let sequence = Rock::sequence();
+            let rock = sequence[self.game.number_of_rocks % sequence.len()];
+            self.game.spawn_new_rock(rock);
+        }
+
+        // every other move is a fall, but it keeps the number of the jet before it
+        let jet_number = (self.moves / 2) % self.jets.len();
+        let direction = if self.moves.is_multiple_of(2) {
+            &self.jets[jet_number]
+        } else {
+            &Direction::Down
+        };
+        self.landed = matches!(self.game.make_move(direction), MoveResult::RockLanded);
+        self.moves += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.moves
+    }
+
+    fn render(&self) -> String {
+        self.game.to_string()
+    }
+}
+
+pub fn solve_part1(input: &[Direction], params: &Params) -> i64 {
+    // no rocks, no tower - and nothing would ever land to stop the run
+    if params.rocks_part1 == 0 {
+        return 0;
+    }
+
+    let mut chamber = Chamber::new(input);
+    chamber.run_until(|c| c.just_landed() == Some(params.rocks_part1));
+    chamber.tower_height()
+}
+
+pub fn solve_part2(input: &[Direction], params: &Params) -> i64 {
+    // no rocks, no tower
+    let Some(last) = params.rocks_part2.checked_sub(1) else {
+        return 0;
+    };
+
+    // drop rocks until the top of the tower looks the same as it did after an
+    // earlier one - the height after that just goes up the same each time round
+    let mut chamber = Chamber::new(input);
+    cycle::metric_at(last, || {
+        chamber.step();
+        chamber.run_until(|c| c.just_landed().is_some());
+        (chamber.state(), chamber.tower_height())
+    })
+}
+
+// a jet pattern `length` pushes long
+pub fn generate(rng: &mut Rng, length: usize) -> String {
+    let mut jets = (0..length.max(1))
+        .map(|_| *rng.choose(&['<', '>']))
+        .collect::<String>();
+    jets.push('\n');
+    jets
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Part1Input = Vec<Direction>;
+    type Part2Input = Vec<Direction>;
+    type Part1Output = i64;
+    type Part2Output = i64;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input, params)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input, params)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day17);
+
+#[test]
+fn test_day17_no_rocks() {
+    let input = input_generator_part1(">>><<><>><<<>\n").unwrap();
+    let params = Params {
+        rocks_part1: 0,
+        rocks_part2: 0,
+    };
+    assert_eq!(solve_part1(&input, &params), 0);
+    assert_eq!(solve_part2(&input, &params), 0);
+}
+
+// part 2 spots where the tower starts repeating itself and works out the height
+// from there - check that against dropping every rock into a plain grid
+#[cfg(test)]
+mod differential {
+    use super::*;
+    use aoc_common::{
+        differential::{self, Differential},
+        rng::Rng,
+    };
+
+    struct CycleExtrapolation;
+
+    // each rock's cells from its bottom left, in the order they fall
+    const SHAPES: [&[(i64, i64)]; 5] = [
+        &[(0, 0), (1, 0), (2, 0), (3, 0)],
+        &[(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+        &[(0, 0), (0, 1), (0, 2), (0, 3)],
+        &[(0, 0), (0, 1), (1, 0), (1, 1)],
+    ];
+
+    impl Differential for CycleExtrapolation {
+        type Input = (Vec<Direction>, usize);
+        type Output = i64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let jets = (0..rng.range(1..=30))
+                .map(|_| *rng.choose(&[Direction::Left, Direction::Right]))
+                .collect();
+            (jets, rng.range(1..=300) as usize)
+        }
+
+        fn shrink((jets, rocks): &Self::Input) -> Vec<Self::Input> {
+            let fewer_jets = differential::shrink_vec(jets, 1)
+                .into_iter()
+                .map(|jets| (jets, *rocks));
+            let fewer_rocks = differential::shrink_number(*rocks as i64)
+                .into_iter()
+                .filter(|&r| r >= 1)
+                .map(|rocks| (jets.clone(), rocks as usize));
+            fewer_jets.chain(fewer_rocks).collect()
+        }
+
+        fn reference((jets, rocks): &Self::Input) -> Self::Output {
+            let mut rows: Vec<[bool; 7]> = Vec::new();
+            let fits = |rows: &Vec<[bool; 7]>, shape: &[(i64, i64)], x: i64, y: i64| {
+                shape.iter().all(|(dx, dy)| {
+                    let (x, y) = (x + dx, y + dy);
+                    (0..7).contains(&x)
+                        && y >= 0
+                        && rows.get(y as usize).is_none_or(|row| !row[x as usize])
+                })
+            };
+
+            let mut jet = 0;
+            for rock in 0..*rocks {
+                let shape = SHAPES[rock % SHAPES.len()];
+                let (mut x, mut y) = (2, rows.len() as i64 + 3);
+                loop {
+                    let push = match jets[jet % jets.len()] {
+                        Direction::Left => -1,
+                        _ => 1,
+                    };
+                    jet += 1;
+                    if fits(&rows, shape, x + push, y) {
+                        x += push;
+                    }
+                    if !fits(&rows, shape, x, y - 1) {
+                        break;
+                    }
+                    y -= 1;
+                }
+                for (dx, dy) in shape {
+                    let row = (y + dy) as usize;
+                    if row >= rows.len() {
+                        rows.resize(row + 1, [false; 7]);
+                    }
+                    rows[row][(x + dx) as usize] = true;
+                }
+            }
+            rows.len() as i64
+        }
+
+        fn optimised((jets, rocks): &Self::Input) -> Self::Output {
+            let params = Params {
+                rocks_part2: *rocks,
+                ..Params::default()
+            };
+            solve_part2(jets, &params)
+        }
+    }
+
+    // the generator's jet patterns, with enough rocks for a well the jets never
+    // fill to get deeper than `MAX_WELL_DEPTH` - past which the cycle detection
+    // stops looking, so it has to still find the right cycle without it
+    struct GeneratedJets;
+
+    impl Differential for GeneratedJets {
+        type Input = (Vec<Direction>, usize);
+        type Output = i64;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let length = rng.range(1..=40) as usize;
+            let jets = input_generator_part1(&super::generate(rng, length)).unwrap();
+            (jets, rng.range(1..=2000) as usize)
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            CycleExtrapolation::shrink(input)
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            CycleExtrapolation::reference(input)
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            CycleExtrapolation::optimised(input)
+        }
+    }
+
+    #[test]
+    fn test_cycle_extrapolation() {
+        differential::check::<CycleExtrapolation>(100);
+    }
+
+    #[test]
+    fn test_generated_jets() {
+        differential::check::<GeneratedJets>(50);
+    }
+
+    #[test]
+    fn test_unfilled_well() {
+        // pushed right every time, nothing ever lands in the two left-hand
+        // columns, so the well there gets deeper with every rock
+        let input = (vec![Direction::Right], 2000);
+        assert_eq!(
+            CycleExtrapolation::optimised(&input),
+            CycleExtrapolation::reference(&input)
+        );
+        // and it still repeats, so the real number of rocks doesn't take forever
+        assert!(solve_part2(&input.0, &Params::default()) > 0);
+    }
+}
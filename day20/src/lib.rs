@@ -0,0 +1,322 @@
+//! Day 20: Grove Positioning System.
+//!
+//! Mixes the encrypted file's numbers by moving each [`Entry`] through the list,
+//! once in part 1 and ten times with the decryption key in part 2.
+
+use aoc_common::{parse, rng::Rng, ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Entry {
+    value: i64,
+    original_index: usize,
+}
+
+impl Entry {
+    pub fn with_value(v: i64, idx: usize) -> Self {
+        Self {
+            value: v,
+            original_index: idx,
+        }
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Entry>, ParseError> {
+    let entries = parse::lines(Day20::DAY, input)
+        .enumerate()
+        .map(|(idx, mut l)| {
+            let value = l.number()?;
+            l.end()?;
+            Ok(Entry::with_value(value, idx))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // the grove coordinates are counted on from the 0
+    if !entries.iter().any(|e| e.value == 0) {
+        return Err(parse::lines(Day20::DAY, input).end_error("a 0 somewhere in the file"));
+    }
+    Ok(entries)
+}
+
+pub fn move_forward_wrapping(len: usize, num: usize) -> usize {
+    if num + 1 < len {
+        num + 1
+    } else {
+        0
+    }
+}
+
+pub fn move_backward_wrapping(len: usize, num: usize) -> usize {
+    if num == 0 {
+        len - 1
+    } else {
+        num - 1
+    }
+}
+
+pub fn shuffle_vector_by_original_index(input: &mut Vec<Entry>) {
+    let number = input.len();
+    // with only one number there's nowhere for it to go
+    if number < 2 {
+        return;
+    }
+    for idx in 0..number {
+        if let Some(index) = input.iter().position(|e| e.original_index == idx) {
+            let entry_to_move = input.get_mut(index).unwrap();
+            // because of how the wraparound works, moving by "length of vector - 1" is the same as
+            // not moving, so mod it out
+            let amount_to_move = entry_to_move.value % ((number as i64) - 1);
+
+            match amount_to_move.cmp(&0) {
+                Ordering::Greater => {
+                    let mut current_location = index;
+                    for _ in 1..=amount_to_move {
+                        // nowhere near the end - move forward
+                        if current_location < number - 2 {
+                            let new_location = current_location + 1;
+                            input.swap(current_location, new_location);
+                            current_location = new_location;
+                        } else if current_location == number - 2 {
+                            // we're "before the final element" - so moving forward one
+                            // will put us "before the first element"; ie at the start
+                            let e = input.remove(current_location);
+                            input.insert(0, e);
+                            current_location = 0;
+                        } else if current_location == number - 1 {
+                            // we're 'before the start' already (but at the end)
+                            // so we now want to move "before the second element",
+                            // ie to position 1
+                            let e = input.remove(current_location);
+                            input.insert(1, e);
+                            current_location = 1;
+                        }
+                    }
+                }
+                Ordering::Equal => continue,
+                Ordering::Less => {
+                    // moving backwards - this is so confusing
+                    let mut current_location = index;
+                    for _ in 1..=(amount_to_move.abs()) {
+                        // haven't got near the start yet
+                        if current_location > 1 {
+                            let new_location = current_location - 1;
+                            input.swap(current_location, new_location);
+                            current_location = new_location;
+                        } else if current_location == 1 {
+                            // we're "after the first element", which means we need to move to
+                            // "after the last element"
+                            let e = input.remove(current_location);
+                            // this makes the vector one shorter - so we have to subtract an extra one here
+                            input.insert(number - 1, e);
+                            current_location = number - 1;
+                        } else if current_location == 0 {
+                            // we're "after the last element" already (but at the start) -
+                            // which means we need to move to after the second-to-last element? I think?
+                            let e = input.remove(current_location);
+                            input.insert(number - 2, e);
+                            current_location = number - 2;
+                        }
+                    }
+                }
+            }
+        } else {
+            // every index up to the length is in there once, so this is a bug
+            panic!("no entry has original index {idx}");
+        }
+    }
+}
+
+pub fn solve_part1(input: &[Entry]) -> i64 {
+    let mut input = input.to_owned();
+    let number = input.len();
+    shuffle_vector_by_original_index(&mut input);
+
+    let zero_index = input
+        .iter()
+        .position(|e| e.value == 0)
+        .expect("the parser checks there's a 0");
+    let first = (zero_index + 1000) % number;
+    let second = (first + 1000) % number;
+    let third = (second + 1000) % number;
+
+    input[first].value + input[second].value + input[third].value
+}
+
+// part 2 decrypts the file properly
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Params {
+    pub decryption_key: i64,
+    pub mixes: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            decryption_key: 811_589_153,
+            mixes: 10,
+        }
+    }
+}
+
+pub fn solve_part2(input: &[Entry], params: &Params) -> i64 {
+    let mut input = input
+        .iter()
+        .map(|e| Entry {
+            value: e.value * params.decryption_key,
+            original_index: e.original_index,
+        })
+        .collect::<Vec<_>>();
+    let number = input.len();
+    for _ in 1..=params.mixes {
+        shuffle_vector_by_original_index(&mut input);
+    }
+
+    let zero_index = input
+        .iter()
+        .position(|e| e.value == 0)
+        .expect("the parser checks there's a 0");
+    let first = (zero_index + 1000) % number;
+    let second = (first + 1000) % number;
+    let third = (second + 1000) % number;
+
+    input[first].value + input[second].value + input[third].value
+}
+
+// a file of `numbers` numbers (at least one), exactly one of which is 0
+pub fn generate(rng: &mut Rng, numbers: usize) -> String {
+    let mut file = (1..numbers.max(1))
+        .map(|_| loop {
+            let n = rng.range(-10_000..=10_000);
+            if n != 0 {
+                break n;
+            }
+        })
+        .collect::<Vec<_>>();
+    file.insert(rng.below(file.len() + 1), 0);
+    file.iter().map(|n| format!("{n}\n")).collect()
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Part1Input = Vec<Entry>;
+    type Part2Input = Vec<Entry>;
+    type Part1Output = i64;
+    type Part2Output = i64;
+    type Params = Params;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input, params)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day20);
+
+#[test]
+fn test_day20_no_zero() {
+    let error = input_generator_part1("1\n2\n-3\n").err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (20, 4, 1));
+    assert_eq!(error.expected, "a 0 somewhere in the file");
+    assert_eq!(error.found, "end of input");
+}
+
+// the mixing moves numbers a swap at a time, with special cases for going round the
+// ends - check it against just taking each number out and putting it back in its
+// new place
+#[cfg(test)]
+mod differential {
+    use super::*;
+    use aoc_common::{
+        differential::{self, Differential},
+        rng::Rng,
+    };
+
+    struct Mixing;
+
+    // the list is a circle, so where it starts doesn't matter - only what comes
+    // after what, going round from the first number
+    fn going_round(original_indexes: impl Iterator<Item = usize>) -> Vec<usize> {
+        let mut order = original_indexes.collect::<Vec<_>>();
+        let start = order.iter().position(|&i| i == 0).unwrap();
+        order.rotate_left(start);
+        order
+    }
+
+    impl Differential for Mixing {
+        type Input = Vec<i64>;
+        type Output = Vec<usize>;
+
+        fn generate(rng: &mut Rng) -> Self::Input {
+            let size = rng.range(1..=10);
+            (0..rng.range(1..=12))
+                .map(|_| {
+                    // sometimes huge, like after the decryption key
+                    if rng.one_in(5) {
+                        rng.range(-1_000_000_000_000..=1_000_000_000_000)
+                    } else {
+                        rng.range(-size..=size)
+                    }
+                })
+                .collect()
+        }
+
+        fn shrink(input: &Self::Input) -> Vec<Self::Input> {
+            let mut smaller = differential::shrink_vec(input, 1);
+            for (i, &n) in input.iter().enumerate() {
+                smaller.extend(differential::shrink_number(n).into_iter().map(|m| {
+                    let mut changed = input.clone();
+                    changed[i] = m;
+                    changed
+                }));
+            }
+            smaller
+        }
+
+        fn reference(input: &Self::Input) -> Self::Output {
+            let mut order = (0..input.len()).collect::<Vec<_>>();
+            for (index, value) in input.iter().enumerate() {
+                let from = order.iter().position(|&i| i == index).unwrap();
+                order.remove(from);
+                let to = match order.len() {
+                    0 => 0,
+                    others => (from as i64 + value).rem_euclid(others as i64) as usize,
+                };
+                order.insert(to, index);
+            }
+            going_round(order.into_iter())
+        }
+
+        fn optimised(input: &Self::Input) -> Self::Output {
+            let mut entries = input
+                .iter()
+                .enumerate()
+                .map(|(index, &value)| Entry::with_value(value, index))
+                .collect();
+            shuffle_vector_by_original_index(&mut entries);
+            going_round(entries.iter().map(|e| e.original_index))
+        }
+    }
+
+    #[test]
+    fn test_mixing() {
+        differential::check::<Mixing>(500);
+    }
+}
@@ -0,0 +1,560 @@
+//! Day 21: Monkey Math.
+//!
+//! Evaluates the [`MonkeyGraph`] of yelling monkeys, then works out what `humn`
+//! has to yell for both sides of `root` to match.
+
+use aoc_common::{
+    parse::{self, Line},
+    rng::Rng,
+    NoParams, ParseError, Solution,
+};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct MonkeyId(String);
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Yell {
+    Specific(i64),
+    Result(MonkeyId, Operation, MonkeyId),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Monkey {
+    id: MonkeyId,
+    yell: Yell,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MonkeyGraph {
+    vertices: HashSet<MonkeyId>,
+    // (m1, m2) means m1 is required for m2
+    edges: HashSet<(MonkeyId, MonkeyId)>,
+}
+
+impl MonkeyGraph {
+    pub fn remove_edge(&mut self, (m1, m2): (MonkeyId, MonkeyId)) {
+        self.edges.remove(&(m1, m2));
+    }
+}
+
+// topological sort of the graph
+pub fn sort_graph(graph: &MonkeyGraph) -> Vec<MonkeyId> {
+    let mut graph: MonkeyGraph = graph.clone();
+    let dependents = graph
+        .edges
+        .iter()
+        .cloned()
+        .map(|(_, m)| m)
+        .collect::<HashSet<_>>();
+    // first find everything that doesn't depend on anything else - these can go first
+    let mut remaining_leaves = graph
+        .vertices
+        .difference(&dependents)
+        .cloned()
+        .collect::<HashSet<_>>();
+    let mut sorted_nodes = vec![];
+    loop {
+        // nothing left to process
+        if remaining_leaves.is_empty() {
+            break;
+        }
+
+        // anything in remaining_leaves can go next, because nothing there depends on anything unprocessed
+        let n = remaining_leaves.iter().next().unwrap().clone();
+        remaining_leaves.remove(&n);
+        sorted_nodes.push(n.clone());
+
+        // find what things depended on the node we just took
+        let edges_from_here = graph
+            .edges
+            .iter()
+            .filter(|&(m, _)| m == &n)
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        for (m1, m2) in edges_from_here {
+            // for each of those: remove the edge from the graph
+            // this means the graph only contains dependencies involving things we haven't processed yet
+            graph.remove_edge((m1.clone(), m2.clone()));
+            if !graph.edges.iter().any(|(_, m)| *m == m2) {
+                // if we just took the last dependency of some m2 out of the graph, then m2 is a leaf
+                // and can go in the sorted list any time from now
+                remaining_leaves.insert(m2);
+            }
+        }
+    }
+
+    sorted_nodes
+}
+
+pub struct Input {}
+
+fn parse_monkey_id(line: &mut Line) -> Result<MonkeyId, ParseError> {
+    let id = line.take_while1("a monkey name", |c| c.is_ascii_lowercase())?;
+    Ok(MonkeyId(id.to_owned()))
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let mut vertices = HashSet::new();
+    let mut edges = HashSet::new();
+    let mut lookup = HashMap::new();
+    // monkeys being listened to, which need checking once we've seen them all
+    let mut listened_to = Vec::new();
+    // each monkey only has the one monkey listening to it, so the yelling goes
+    // up a tree to root - and part 2's humn is only ever on one side of it
+    let mut has_listener = HashSet::from([MonkeyId("root".to_owned())]);
+    let mut listen = |line: &mut Line, listened_to: &mut Vec<_>| {
+        let position = line.position();
+        let monkey = parse_monkey_id(line)?;
+        if !has_listener.insert(monkey.clone()) {
+            return Err(line.error_at(position, "a monkey nobody else is listening to"));
+        }
+        listened_to.push((
+            monkey.clone(),
+            line.error_at(position, "a monkey that exists"),
+        ));
+        Ok(monkey)
+    };
+    for mut line in parse::lines(Day21::DAY, input) {
+        let monkey_id = parse_monkey_id(&mut line)?;
+        if lookup.contains_key(&monkey_id) {
+            return Err(line.error_at(0, "a monkey that hasn't yelled already"));
+        }
+        line.tag(": ")?;
+        if line
+            .rest()
+            .starts_with(|c: char| c.is_ascii_digit() || c == '-')
+        {
+            let num = line.number()?;
+            line.end()?;
+            let monkey = Monkey {
+                id: monkey_id.clone(),
+                yell: Yell::Specific(num),
+            };
+            vertices.insert(monkey_id.clone());
+            lookup.insert(monkey_id, monkey);
+        } else {
+            let left_monkey = listen(&mut line, &mut listened_to)?;
+            line.tag(" ")?;
+            let operation = line.one_of(&[
+                ("+", Operation::Add),
+                ("-", Operation::Sub),
+                ("*", Operation::Mul),
+                ("/", Operation::Div),
+            ])?;
+            line.tag(" ")?;
+            let right_monkey = listen(&mut line, &mut listened_to)?;
+            line.end()?;
+
+            vertices.insert(monkey_id.clone());
+            edges.insert((left_monkey.clone(), monkey_id.clone()));
+            edges.insert((right_monkey.clone(), monkey_id.clone()));
+            let monkey = Monkey {
+                id: monkey_id.clone(),
+                yell: Yell::Result(left_monkey.clone(), operation, right_monkey.clone()),
+            };
+            lookup.insert(monkey_id, monkey);
+        }
+    }
+
+    if let Some((_, error)) = listened_to
+        .into_iter()
+        .find(|(monkey_id, _)| !lookup.contains_key(monkey_id))
+    {
+        return Err(error);
+    }
+    if !lookup.contains_key(&MonkeyId("root".to_owned())) {
+        return Err(parse::lines(Day21::DAY, input).end_error("a monkey called `root`"));
+    }
+
+    let graph = MonkeyGraph { vertices, edges };
+    let sorted_ids = sort_graph(&graph);
+    Ok(sorted_ids
+        .into_iter()
+        .map(|id| lookup.get(&id).unwrap().clone())
+        .collect())
+}
+
+// part 2 also needs root to be listening to two monkeys, with humn somewhere
+// underneath one of them
+pub fn input_generator_part2(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let monkeys = input_generator_part1(input)?;
+    let line_for = |name: &str| {
+        parse::lines(Day21::DAY, input).find(|l| {
+            l.text()
+                .strip_prefix(name)
+                .is_some_and(|rest| rest.starts_with(':'))
+        })
+    };
+
+    let lookup = monkeys
+        .iter()
+        .map(|m| (m.id.clone(), m))
+        .collect::<HashMap<_, _>>();
+    let root = lookup[&MonkeyId("root".to_owned())];
+    if !matches!(root.yell, Yell::Result(..)) {
+        let line = line_for("root").expect("the part 1 parser found root");
+        return Err(line.error_at(6, "two monkeys for `root` to listen to"));
+    }
+
+    let human = MonkeyId("humn".to_owned());
+    let mut to_visit = vec![&root.id];
+    while let Some(id) = to_visit.pop() {
+        if *id == human {
+            return Ok(monkeys);
+        }
+        if let Yell::Result(m1, _, m2) = &lookup[id].yell {
+            to_visit.extend([m1, m2]);
+        }
+    }
+    Err(match line_for("humn") {
+        Some(line) => line.error_at(0, "a `humn` that `root` is listening out for"),
+        None => parse::lines(Day21::DAY, input).end_error("a monkey called `humn`"),
+    })
+}
+
+pub fn solve_part1(input: &Vec<Monkey>) -> i64 {
+    let mut results = HashMap::new();
+    // input is already in order, so just go through and build the results
+    for m in input {
+        match &m.yell {
+            Yell::Specific(num) => {
+                results.insert(m.id.clone(), *num);
+            }
+            Yell::Result(m1, op, m2) => {
+                let m1_result = *results.get(m1).unwrap();
+                let m2_result = *results.get(m2).unwrap();
+                match op {
+                    Operation::Add => {
+                        results.insert(m.id.clone(), m1_result + m2_result);
+                    }
+                    Operation::Sub => {
+                        results.insert(m.id.clone(), m1_result - m2_result);
+                    }
+                    Operation::Mul => {
+                        results.insert(m.id.clone(), m1_result * m2_result);
+                    }
+                    Operation::Div => {
+                        results.insert(m.id.clone(), m1_result / m2_result);
+                    }
+                }
+            }
+        }
+    }
+
+    // the parser checks there's a root
+    *results.get(&MonkeyId("root".to_owned())).unwrap()
+}
+
+pub fn solve_part2(input: &Vec<Monkey>) -> i64 {
+    let mut partial_results = HashMap::new();
+    let human = MonkeyId("humn".to_owned());
+    let root = MonkeyId("root".to_owned());
+    // a bit trickier here - use Option to track calculations we don't know the answer to yet
+    for m in input {
+        if m.id.clone() == human {
+            // human doesn't know what to say
+            partial_results.insert(m.id.clone(), None);
+        } else {
+            match &m.yell {
+                Yell::Specific(num) => {
+                    partial_results.insert(m.id.clone(), Some(*num));
+                }
+                Yell::Result(m1, op, m2) => {
+                    let m1_result = *partial_results.get(m1).unwrap();
+                    let m2_result = *partial_results.get(m2).unwrap();
+                    match op {
+                        // map the operation into the Options
+                        Operation::Add => {
+                            partial_results.insert(
+                                m.id.clone(),
+                                m1_result.and_then(|x| m2_result.map(|y| x + y)),
+                            );
+                        }
+                        Operation::Sub => {
+                            partial_results.insert(
+                                m.id.clone(),
+                                m1_result.and_then(|x| m2_result.map(|y| x - y)),
+                            );
+                        }
+                        Operation::Mul => {
+                            partial_results.insert(
+                                m.id.clone(),
+                                m1_result.and_then(|x| m2_result.map(|y| x * y)),
+                            );
+                        }
+                        Operation::Div => {
+                            partial_results.insert(
+                                m.id.clone(),
+                                m1_result.and_then(|x| m2_result.map(|y| x / y)),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // at this point the results are complete for anything that doesn't require my input
+    // but None for anything that does
+    // now we go back in the opposite direction and figure out what all the answers should have been
+    let mut monkeys = input.clone();
+    monkeys.reverse();
+
+    for m in monkeys {
+        if m.id.clone() == root {
+            let Yell::Result(m1, _, m2) = m.yell else {
+                unreachable!("the parser checks root listens to two monkeys")
+            };
+            let m1_result = *partial_results.get(&m1).unwrap();
+            let m2_result = *partial_results.get(&m2).unwrap();
+            match (m1_result, m2_result) {
+                // the other number should be the same, so tell that monkey what its answer was supposed to be
+                (Some(num), None) => {
+                    partial_results.insert(m2, Some(num));
+                }
+                (None, Some(num)) => {
+                    partial_results.insert(m1, Some(num));
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            // we must have reverse-calculated everything this far at least
+            let Some(m_result) = *partial_results.get(&m.id).unwrap() else {
+                unreachable!()
+            };
+            match &m.yell {
+                Yell::Specific(_) => {
+                    // nothing to do - monkey knows its number
+                }
+                Yell::Result(m1, op, m2) => {
+                    let m1_result = *partial_results.get(m1).unwrap();
+                    let m2_result = *partial_results.get(m2).unwrap();
+                    match (m1_result, m2_result) {
+                        (Some(_), Some(_)) => {
+                            // both this monkey's dependents already know their answers - no need to do anything
+                        }
+                        (Some(num1), None) => {
+                            // first monkey knows, but we don't know what the second monkey should have said
+                            // figure it out from the answer and the first monkey, and then remember what
+                            // it was supposed to calculate
+                            match op {
+                                Operation::Add => {
+                                    partial_results.insert(m2.clone(), Some(m_result - num1));
+                                }
+                                Operation::Sub => {
+                                    partial_results.insert(m2.clone(), Some(num1 - m_result));
+                                }
+                                Operation::Mul => {
+                                    partial_results.insert(m2.clone(), Some(m_result / num1));
+                                }
+                                Operation::Div => {
+                                    partial_results.insert(m2.clone(), Some(num1 / m_result));
+                                }
+                            }
+                        }
+                        (None, Some(num2)) => {
+                            // same as above, but monkeys the other way round
+                            match op {
+                                Operation::Add => {
+                                    partial_results.insert(m1.clone(), Some(m_result - num2));
+                                }
+                                Operation::Sub => {
+                                    partial_results.insert(m1.clone(), Some(num2 + m_result));
+                                }
+                                Operation::Mul => {
+                                    partial_results.insert(m1.clone(), Some(m_result / num2));
+                                }
+                                Operation::Div => {
+                                    partial_results.insert(m1.clone(), Some(num2 * m_result));
+                                }
+                            }
+                        }
+                        // can't happen (human is only in one branch)
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+
+    // everything filled in now - what should human have said?
+    partial_results.get(&human).unwrap().unwrap()
+}
+
+// about `monkeys` monkeys. it's built from the top down, starting from what both
+// sides of root have to come to, so every division comes out exact and there's
+// always a whole number for humn to yell. humn's path up to root only adds,
+// subtracts and multiplies, like the real puzzle's
+pub fn generate(rng: &mut Rng, monkeys: usize) -> String {
+    fn name(rng: &mut Rng, names: &mut HashSet<String>) -> String {
+        loop {
+            let name = (0..4)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect::<String>();
+            if names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    // a monkey that yells `value`, listening to `size` monkeys in all
+    fn yelling(
+        rng: &mut Rng,
+        names: &mut HashSet<String>,
+        lines: &mut Vec<String>,
+        value: i64,
+        size: usize,
+    ) -> String {
+        let monkey = name(rng, names);
+        if size < 3 {
+            lines.push(format!("{monkey}: {value}"));
+            return monkey;
+        }
+        let c = rng.range(2..=9);
+        let (left, op, right) = match rng.below(4) {
+            0 => {
+                let left = rng.range(-1000..=1000);
+                (left, '+', value - left)
+            }
+            1 => {
+                let right = rng.range(-1000..=1000);
+                (value + right, '-', right)
+            }
+            2 if value % c == 0 => (value / c, '*', c),
+            _ if value.abs() < 1_000_000_000 => (value * c, '/', c),
+            _ => (value, '+', 0),
+        };
+        let left_size = 1 + rng.below(size - 2);
+        let left = yelling(rng, names, lines, left, left_size);
+        let right = yelling(rng, names, lines, right, size - 1 - left_size);
+        lines.push(format!("{monkey}: {left} {op} {right}"));
+        monkey
+    }
+
+    let mut names = HashSet::from(["root".to_string(), "humn".to_string()]);
+    let mut lines = Vec::new();
+    let target = rng.range(1..=1_000_000);
+    let mut budget = monkeys.saturating_sub(2);
+    let other_side = yelling(rng, &mut names, &mut lines, target, (budget / 2).max(1));
+    budget -= budget / 2;
+
+    // then down from root to humn, a monkey at a time - each one listens to the
+    // one below it, and to a few others that don't depend on humn. multiplying is
+    // kept in check, so a different humn in part 1 can't make anything overflow
+    fn next(rng: &mut Rng, names: &mut HashSet<String>, budget: usize) -> String {
+        match budget {
+            0 | 1 => "humn".to_string(),
+            _ => name(rng, names),
+        }
+    }
+    let mut current = next(rng, &mut names, budget);
+    lines.push(format!("root: {other_side} + {current}"));
+    let (mut needed, mut multiplied) = (target, 1);
+    while current != "humn" {
+        let size = rng.range(1..=(budget as i64 - 1).min(5)) as usize;
+        budget -= 1 + size;
+        let factor = rng.range(2..=9);
+        let constant = rng.range(-1000..=1000);
+        // (what the other monkeys yell, the operation, what the monkey below has
+        // to yell, and whether the other monkeys come first)
+        let (constant, op, below, constant_first) = match rng.below(4) {
+            0 if needed % factor == 0 && multiplied * factor <= 1_000_000 => {
+                multiplied *= factor;
+                (factor, '*', needed / factor, rng.one_in(2))
+            }
+            0 | 1 => (constant, '+', needed - constant, rng.one_in(2)),
+            2 => (constant, '-', needed + constant, false),
+            _ => (constant, '-', constant - needed, true),
+        };
+        let constant = yelling(rng, &mut names, &mut lines, constant, size);
+        let below_name = next(rng, &mut names, budget);
+        lines.push(if constant_first {
+            format!("{current}: {constant} {op} {below_name}")
+        } else {
+            format!("{current}: {below_name} {op} {constant}")
+        });
+        current = below_name;
+        needed = below;
+    }
+    lines.push(format!("humn: {}", rng.range(1..=5000)));
+
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Part1Input = Vec<Monkey>;
+    type Part2Input = Vec<Monkey>;
+    type Part1Output = i64;
+    type Part2Output = i64;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part2(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day21);
+
+#[test]
+fn test_day21_bad_monkeys() {
+    let error = input_generator_part1("root: aaaa + bbbb\naaaa: 1\nbbbb: 2\naaaa: 3\n")
+        .err()
+        .unwrap();
+    assert_eq!((error.day, error.line, error.column), (21, 4, 1));
+    assert_eq!(error.expected, "a monkey that hasn't yelled already");
+
+    // the same monkey on both sides
+    let error = input_generator_part1("root: aaaa + aaaa\naaaa: 1\n")
+        .err()
+        .unwrap();
+    assert_eq!((error.line, error.column), (1, 14));
+    assert_eq!(error.expected, "a monkey nobody else is listening to");
+
+    let error = input_generator_part1("aaaa: bbbb + cccc\nbbbb: 1\ncccc: 2\n")
+        .err()
+        .unwrap();
+    assert_eq!((error.line, error.column), (4, 1));
+    assert_eq!(error.expected, "a monkey called `root`");
+
+    // fine for part 1, but part 2 needs root listening to humn
+    let input = "root: aaaa + bbbb\naaaa: 1\nbbbb: 2\nhumn: 3\n";
+    assert_eq!(solve_part1(&input_generator_part1(input).unwrap()), 3);
+    let error = input_generator_part2(input).err().unwrap();
+    assert_eq!((error.line, error.column), (4, 1));
+    assert_eq!(error.expected, "a `humn` that `root` is listening out for");
+
+    let error = input_generator_part2("root: 5\n").err().unwrap();
+    assert_eq!((error.line, error.column), (1, 7));
+    assert_eq!(error.expected, "two monkeys for `root` to listen to");
+}
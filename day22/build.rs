@@ -0,0 +1,4 @@
+fn main() {
+    // a test for each of the puzzle examples in examples/
+    aoc_common::examples::generate_tests();
+}
@@ -0,0 +1,330 @@
+//! Day 23: Unstable Diffusion.
+//!
+//! Spreads the [`Elf`]s out over a number of rounds, counting the empty ground
+//! after ten rounds and the round where nobody moves.
+
+use aoc_common::{parse, rng::Rng, NoParams, ParseError, Simulation, Solution};
+use std::collections::{HashSet, VecDeque};
+
+pub type Coord = aoc_common::Coord<i64>;
+
+fn neighbours(coord: Coord) -> Vec<Coord> {
+    vec![
+        coord + (0, 1),
+        coord + (1, 1),
+        coord + (1, 0),
+        coord + (1, -1),
+        coord + (0, -1),
+        coord + (-1, -1),
+        coord + (-1, 0),
+        coord + (-1, 1),
+    ]
+}
+
+// ---> increasing x
+// |
+// v
+// increasing y
+fn neighbours_in_direction(coord: Coord, direction: &Direction) -> Vec<Coord> {
+    match direction {
+        Direction::West => vec![coord + (-1, -1), coord + (-1, 0), coord + (-1, 1)],
+        Direction::East => vec![coord + (1, -1), coord + (1, 0), coord + (1, 1)],
+        Direction::North => vec![coord + (-1, -1), coord + (0, -1), coord + (1, -1)],
+        Direction::South => vec![coord + (-1, 1), coord + (0, 1), coord + (1, 1)],
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn as_delta(&self) -> (i64, i64) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+            Direction::East => (1, 0),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Elf {
+    current_coord: Coord,
+    // proposed_direction: Option<Direction>,
+    proposed_coord: Option<Coord>,
+}
+
+#[derive(Clone)]
+pub struct Input {
+    elves: Vec<Elf>,
+}
+
+impl Input {
+    pub fn bounding_x_range(&self) -> (i64, i64) {
+        let min_x = self.elves.iter().map(|e| e.current_coord.x).min().unwrap();
+        let max_x = self.elves.iter().map(|e| e.current_coord.x).max().unwrap();
+        (min_x, max_x)
+    }
+
+    pub fn bounding_y_range(&self) -> (i64, i64) {
+        let min_y = self.elves.iter().map(|e| e.current_coord.y).min().unwrap();
+        let max_y = self.elves.iter().map(|e| e.current_coord.y).max().unwrap();
+        (min_y, max_y)
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let map = self
+            .elves
+            .iter()
+            .map(|e| e.current_coord)
+            .collect::<HashSet<_>>();
+        let (min_y, max_y) = self.bounding_y_range();
+        let (min_x, max_x) = self.bounding_x_range();
+
+        for row in min_y..=max_y {
+            for col in min_x..=max_x {
+                if map.contains(&(col, row).into()) {
+                    write!(f, "#")?
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Input, ParseError> {
+    let mut elves = Vec::new();
+    for (row_number, mut line) in parse::lines(Day23::DAY, input).enumerate() {
+        let mut col_number = 0;
+        while !line.is_empty() {
+            let coord = (col_number as i64, row_number as i64).into();
+            if line.one_of(&[("#", true), (".", false)])? {
+                elves.push(Elf {
+                    current_coord: coord,
+                    proposed_coord: None,
+                });
+            }
+            col_number += 1;
+        }
+    }
+    // with nobody there, there'd be no rectangle for part 1 to count inside
+    if elves.is_empty() {
+        return Err(parse::lines(Day23::DAY, input).end_error("an elf `#`"));
+    }
+
+    Ok(Input { elves })
+}
+
+pub fn step(elves: &mut [Elf], direction_order: &VecDeque<Direction>) -> bool {
+    let mut moved = false;
+    // part 1: propose a move
+    let current_elf_locations = elves
+        .iter()
+        .map(|e| e.current_coord)
+        .collect::<HashSet<_>>();
+    let mut proposed_elf_locations = HashSet::new();
+    let mut duplicate_proposed_elf_locations = HashSet::new();
+    for elf in &mut *elves {
+        if neighbours(elf.current_coord)
+            .iter()
+            .any(|c| current_elf_locations.contains(c))
+        {
+            'directions: for d in direction_order {
+                if neighbours_in_direction(elf.current_coord, d)
+                    .iter()
+                    .any(|c| current_elf_locations.contains(c))
+                {
+                    // there's already an elf over there - don't go that way
+                } else {
+                    // found a direction with no elves: move that way
+                    let new_coord = elf.current_coord + d.as_delta();
+                    elf.proposed_coord = Some(new_coord);
+                    // remember it, to check for duplicates
+                    if !proposed_elf_locations.insert(new_coord) {
+                        // one elf has already tried to go here - remember this as a duplicate
+                        duplicate_proposed_elf_locations.insert(new_coord);
+                    }
+                    break 'directions;
+                }
+            }
+            // has at least one elf nearby - needs to move
+        } else {
+            // for now use 'none' to mean 'won't move' as well as 'hasn't chosen yet'
+            elf.proposed_coord = None;
+        }
+    }
+
+    // part 2: move elves (but only if they were the only elf to suggest moving there)
+    for elf in &mut *elves {
+        match elf.proposed_coord {
+            None => {
+                // do nothing - current coord is still fine
+            }
+            Some(new_coord) => {
+                if !duplicate_proposed_elf_locations.contains(&new_coord) {
+                    elf.current_coord = new_coord;
+                    moved = true;
+                } else {
+                    // can't move
+                }
+                // reset for next step
+                elf.proposed_coord = None;
+            }
+        }
+    }
+
+    moved
+}
+
+// the elves spreading out, a round at a time
+pub struct Grove {
+    elves: Input,
+    direction_order: VecDeque<Direction>,
+    rounds: usize,
+}
+
+impl Grove {
+    pub fn new(input: &Input) -> Self {
+        Self {
+            elves: input.clone(),
+            direction_order: VecDeque::from([
+                Direction::North,
+                Direction::South,
+                Direction::West,
+                Direction::East,
+            ]),
+            rounds: 0,
+        }
+    }
+
+    pub fn elves(&self) -> &Input {
+        &self.elves
+    }
+
+    // the ground in the smallest rectangle around the elves that nobody's standing on
+    pub fn empty_ground(&self) -> i64 {
+        let (min_y, max_y) = self.elves.bounding_y_range();
+        let (min_x, max_x) = self.elves.bounding_x_range();
+        // both bounds are inclusive
+        let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+        area - (self.elves.elves.len() as i64)
+    }
+}
+
+impl Simulation for Grove {
+    // one round - it's finished once nobody moves
+    fn step(&mut self) -> bool {
+        let moved = step(&mut self.elves.elves, &self.direction_order);
+        // move the directions
+        self.direction_order.rotate_left(1);
+        self.rounds += 1;
+        moved
+    }
+
+    fn steps(&self) -> usize {
+        self.rounds
+    }
+
+    fn render(&self) -> String {
+        self.elves.to_string()
+    }
+}
+
+pub fn solve_part1(input: &Input) -> i64 {
+    let mut grove = Grove::new(input);
+    grove.run_for(10);
+    grove.empty_ground()
+}
+
+pub fn solve_part2(input: &Input) -> usize {
+    let mut grove = Grove::new(input);
+    grove.run();
+    grove.steps()
+}
+
+// a `size` by `size` patch of ground, about half of it elves - and always one
+// in the middle, as the solver needs at least one elf to work out the bounds
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    (0..size)
+        .map(|y| {
+            let mut row = (0..size)
+                .map(|x| {
+                    if (x, y) == (size / 2, size / 2) || rng.one_in(2) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>();
+            row.push('\n');
+            row
+        })
+        .collect()
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Part1Input = Input;
+    type Part2Input = Input;
+    type Part1Output = i64;
+    type Part2Output = usize;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day23);
+
+#[test]
+fn test_grove_rounds() {
+    let input = input_generator_part1(".....\n..##.\n..#..\n.....\n..##.\n.....\n").unwrap();
+    let mut grove = Grove::new(&input);
+    assert!(grove.step());
+    assert_eq!(grove.render(), "##\n..\n#.\n.#\n#.\n");
+
+    grove.run();
+    assert_eq!(grove.steps(), 4);
+    assert_eq!(grove.render(), "..#..\n....#\n#....\n....#\n.....\n..#..\n");
+}
+
+#[test]
+fn test_day23_no_elves() {
+    let error = input_generator_part1("...\n...\n").err().unwrap();
+    assert_eq!((error.day, error.line, error.column), (23, 3, 1));
+    assert_eq!(error.expected, "an elf `#`");
+    assert_eq!(error.found, "end of input");
+}
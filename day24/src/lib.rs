@@ -0,0 +1,354 @@
+//! Day 24: Blizzard Basin.
+//!
+//! Finds the fastest way across the valley between the [`Blizzard`]s, and then
+//! there, back and there again.
+
+use aoc_common::{
+    grid::Position, parse, rng::Rng, Grid, NoParams, ParseError, Simulation, Solution,
+};
+use num::integer::lcm;
+use std::collections::{HashMap, HashSet};
+
+pub type Coord = aoc_common::Coord<u8>;
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Blizzard {
+    current_coord: Coord,
+    direction: Direction,
+}
+
+#[derive(Clone)]
+pub struct Input {
+    blizzards: Vec<Blizzard>,
+    height: u8,
+    width: u8,
+    // how many minutes the blizzards have moved on since the start
+    minute: usize,
+}
+
+impl Input {
+    pub fn move_blizzards(&mut self) {
+        for b in &mut *self.blizzards {
+            let c = b.current_coord;
+            let new_position = match b.direction {
+                // blizzards do wrap around
+                Direction::Up => {
+                    if c.y == 1 {
+                        (c.x, self.height).into()
+                    } else {
+                        (c.x, c.y - 1).into()
+                    }
+                }
+                Direction::Down => {
+                    if c.y == self.height {
+                        (c.x, 1).into()
+                    } else {
+                        (c.x, c.y + 1).into()
+                    }
+                }
+                Direction::Left => {
+                    if c.x == 1 {
+                        (self.width, c.y).into()
+                    } else {
+                        (c.x - 1, c.y).into()
+                    }
+                }
+                Direction::Right => {
+                    if c.x == self.width {
+                        (1, c.y).into()
+                    } else {
+                        (c.x + 1, c.y).into()
+                    }
+                }
+            };
+            b.current_coord = new_position;
+        }
+    }
+
+    pub fn get_elf_moves_from(&self, c: Coord) -> Vec<Coord> {
+        let mut ret = Vec::with_capacity(5);
+        ret.push(c);
+        // elves can't wrap around
+        if c.x > 1 {
+            ret.push((c.x - 1, c.y).into());
+        }
+        if c.x < self.width {
+            ret.push((c.x + 1, c.y).into());
+        }
+        if c.y > 1 {
+            ret.push((c.x, c.y - 1).into());
+        }
+        if c.y < self.height {
+            ret.push((c.x, c.y + 1).into());
+        }
+        ret
+    }
+}
+
+impl std::fmt::Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // just the inside of the valley, without the walls
+        let mut valley = Grid::filled(self.width as usize, self.height as usize, Vec::new());
+        for b in &self.blizzards {
+            let c = b.current_coord;
+            valley[Position {
+                x: c.x as usize - 1,
+                y: c.y as usize - 1,
+            }]
+            .push(b.direction);
+        }
+
+        let rendered = valley.render(|v| match v[..] {
+            [] => '.',
+            [Direction::Up] => '^',
+            [Direction::Down] => 'v',
+            [Direction::Left] => '<',
+            [Direction::Right] => '>',
+            _ => char::from_digit(v.len() as u32, 10).unwrap_or('*'),
+        });
+        write!(f, "{rendered}")
+    }
+}
+
+// the blizzards never stop, so this runs for as long as it's asked to
+impl Simulation for Input {
+    fn step(&mut self) -> bool {
+        self.move_blizzards();
+        self.minute += 1;
+        true
+    }
+
+    fn steps(&self) -> usize {
+        self.minute
+    }
+
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+// find the fastest time from the start to the target.
+// this assumes we start off one square away from the start in a tile that will never be
+// hit by a blizzard - so it will take us 1 minute to move to the start - and it assumes
+// our 'real' target is similar, but one square away from the target passed into this method.
+pub fn find_fastest_time(input: &mut Input, start: Coord, target: Coord) -> i32 {
+    let no_options = HashSet::new();
+
+    // the blizzards repeat after this long - if we were somewhere this long ago, there's
+    // no point in going there again (we'd just repeat; would be better off with another
+    // path from that square back then)
+    // this is 600 for me, I think
+    let repeat_time = lcm(input.height as i32, input.width as i32);
+
+    // remember where we could have got to, each minute.
+    let mut possible_locations_at_time = HashMap::new();
+    // at minute 0, there's nowhere we can be (we aren't in the grid yet)
+    possible_locations_at_time.insert(0, HashSet::<Coord>::new());
+
+    for minute in 1.. {
+        // see where we could have been last minute
+        let places_last_minute = possible_locations_at_time.get(&(minute - 1)).unwrap();
+        // move the blizzards forward - see where they end up (we can't move to those squares)
+        input.step();
+        let blizzard_locations = input
+            .blizzards
+            .iter()
+            .map(|b| b.current_coord)
+            .collect::<HashSet<_>>();
+        // see where we were one blizzard cycle ago - no point going back to any of those squares either
+        // (we'd just form a pointless loop)
+        let positions_one_repeat_ago = if minute < repeat_time {
+            &no_options
+        } else {
+            possible_locations_at_time
+                .get(&(minute - repeat_time))
+                .unwrap()
+        };
+
+        let positions_reachable_at_current_minute = places_last_minute
+            .iter()
+            // everywhere we can move to from last time
+            .flat_map(|c| input.get_elf_moves_from(*c))
+            // plus the first square (could always wait indefinitely at start before moving here)
+            .chain(std::iter::once(start))
+            // can't go anywhere a blizzard is
+            .filter(|c| !blizzard_locations.contains(c))
+            // shouldn't go anywhere we were one blizzard cycle ago
+            // (I think we actually never hit this, but for a bigger board maybe we would)
+            .filter(|c| !positions_one_repeat_ago.contains(c))
+            .collect::<HashSet<_>>();
+
+        // this is the first minute we could have made it to the target - bail out
+        // (it takes us one more minute to reach the 'real' target)
+        if positions_reachable_at_current_minute.contains(&target) {
+            return minute + 1;
+        }
+
+        // otherwise remember our new options and keep going
+        possible_locations_at_time.insert(minute, positions_reachable_at_current_minute);
+    }
+
+    unreachable!()
+}
+
+pub fn input_generator_part1(input: &str) -> Result<Input, ParseError> {
+    let mut lines = parse::lines(Day24::DAY, input);
+    let valley = Grid::from_lines(&mut lines, "`#`, `.`, `^`, `v`, `<` or `>`", |c| match c {
+        '#' | '.' => Some(None),
+        '^' => Some(Some(Direction::Up)),
+        'v' => Some(Some(Direction::Down)),
+        '>' => Some(Some(Direction::Right)),
+        '<' => Some(Some(Direction::Left)),
+        _ => None,
+    })?;
+
+    // ignore the # borders on the input - only consider the inner grid
+    if valley.height() < 3 {
+        return Err(lines.end_error("another row of the valley"));
+    }
+    // coordinates have to fit in a u8
+    if valley.width() > u8::MAX as usize || valley.height() > u8::MAX as usize {
+        return Err(lines.end_error("a valley no bigger than 255x255"));
+    }
+
+    let blizzards = valley
+        .iter()
+        .filter_map(|(position, direction)| {
+            direction.map(|direction| Blizzard {
+                current_coord: (position.x as u8, position.y as u8).into(),
+                direction,
+            })
+        })
+        .collect();
+
+    Ok(Input {
+        blizzards,
+        width: (valley.width() - 2) as u8,
+        height: (valley.height() - 2) as u8,
+        minute: 0,
+    })
+}
+
+pub fn solve_part1(input: &Input) -> i32 {
+    let mut input = input.clone();
+    let (width, height) = (input.width, input.height);
+    find_fastest_time(&mut input, (1, 1).into(), (width, height).into())
+}
+
+pub fn solve_part2(input: &Input) -> i32 {
+    let mut input = input.clone();
+    let (width, height) = (input.width, input.height);
+
+    // fastest route there, back, and there again is the same as doing each route as fast
+    // as possible.
+    // even if it's better to wait a bit before starting the journey back, there's no point
+    // getting to the original target any later - any extra time spent on a longer journey
+    // there could have just been spent waiting at the target before setting off again,
+    // and our logic already handles that.
+
+    let fastest_time_to_end = find_fastest_time(&mut input, (1, 1).into(), (width, height).into());
+    // for the repeat journey, we want to reuse the blizzard state from where it ended up
+    // (but we have to fast-forward by one minute, to account for our move to the 'real' target)
+    input.step();
+    // we _don't_ want to reuse any of our move history, though, because this is a different journey
+    let fastest_time_back = find_fastest_time(&mut input, (width, height).into(), (1, 1).into());
+    // asame again
+    input.step();
+    let fastest_time_to_end_again =
+        find_fastest_time(&mut input, (1, 1).into(), (width, height).into());
+    fastest_time_to_end + fastest_time_back + fastest_time_to_end_again
+}
+
+// a valley `width` squares across (between 3 and 253, so it fits in a u8 with its
+// walls) and a fifth as deep. random blizzards can box the way through in for
+// good, so they're kept to a pattern that always leaves one: blizzards only go
+// left and right along every other row, and up and down every other column, and
+// none start off on the top row or in the last column. then the top row and the
+// last column alternate between squares no blizzard ever reaches and squares
+// they only pass through - somewhere to wait, and then a gap to step through
+pub fn generate(rng: &mut Rng, width: usize) -> String {
+    let width = width.clamp(3, 253);
+    let height = (width / 5).max(3);
+    let mut input = format!("#.{}\n", "#".repeat(width));
+    for y in 0..height {
+        input.push('#');
+        for x in 0..width {
+            let mut directions = Vec::new();
+            if y % 2 == 1 && x < width - 1 {
+                directions.extend(['<', '>']);
+            }
+            if x % 2 == 1 && x < width - 1 && y > 0 {
+                directions.extend(['^', 'v']);
+            }
+            input.push(if !directions.is_empty() && !rng.one_in(4) {
+                *rng.choose(&directions)
+            } else {
+                '.'
+            });
+        }
+        input += "#\n";
+    }
+    input += &format!("{}.#\n", "#".repeat(width));
+    input
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u8 = 24;
+
+    type Part1Input = Input;
+    type Part2Input = Input;
+    type Part1Output = i32;
+    type Part2Output = i32;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(input: &str) -> Result<Self::Part2Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        solve_part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day24);
+
+#[test]
+fn test_day24_display() {
+    let input = r#"#.#####
+#.....#
+#>....#
+#.....#
+#...v.#
+#.....#
+#####.#
+"#;
+
+    let mut valley = input_generator_part1(input).unwrap();
+    valley.move_blizzards();
+    valley.move_blizzards();
+    valley.move_blizzards();
+
+    assert_eq!(valley.to_string(), ".....\n...2.\n.....\n.....\n.....\n");
+}
@@ -0,0 +1,238 @@
+//! Day 25: Full of Hot Air.
+//!
+//! Adds up the fuel requirements as [`SnafuNumber`]s, which use balanced base 5.
+
+use std::iter::Sum;
+use std::{ops::Add, str::FromStr};
+
+use aoc_common::{parse, rng::Rng, solution::NoAnswer, NoParams, ParseError, Solution};
+use itertools::EitherOrBoth;
+use itertools::Itertools;
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Digit {
+    One,
+    Two,
+    Zero,
+    Minus,
+    DoubleMinus,
+}
+
+impl Add for Digit {
+    type Output = (Digit, Digit);
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match (self, rhs) {
+            // adding zero does nothing
+            (Digit::Zero, d) | (d, Digit::Zero) => (d, Digit::Zero),
+            // adding these to each other cancels
+            (Digit::One, Digit::Minus) | (Digit::Minus, Digit::One) => (Digit::Zero, Digit::Zero),
+            (Digit::Two, Digit::DoubleMinus) | (Digit::DoubleMinus, Digit::Two) => {
+                (Digit::Zero, Digit::Zero)
+            }
+            // these part-cancel
+            (Digit::One, Digit::DoubleMinus) | (Digit::DoubleMinus, Digit::One) => {
+                (Digit::Minus, Digit::Zero)
+            }
+            (Digit::Two, Digit::Minus) | (Digit::Minus, Digit::Two) => (Digit::One, Digit::Zero),
+            // these combine but don't overflow
+            (Digit::One, Digit::One) => (Digit::Two, Digit::Zero),
+            (Digit::Minus, Digit::Minus) => (Digit::DoubleMinus, Digit::Zero),
+            // these overflow and give a carry
+            (Digit::One, Digit::Two) | (Digit::Two, Digit::One) => (Digit::DoubleMinus, Digit::One),
+            (Digit::Minus, Digit::DoubleMinus) | (Digit::DoubleMinus, Digit::Minus) => {
+                (Digit::Two, Digit::Minus)
+            }
+            (Digit::Two, Digit::Two) => (Digit::Minus, Digit::One),
+            (Digit::DoubleMinus, Digit::DoubleMinus) => (Digit::One, Digit::Minus),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SnafuNumber {
+    // stored in units-first order
+    digits: Vec<Digit>,
+}
+
+impl Add for SnafuNumber {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut digits = Vec::new();
+        let mut previous_carry = Digit::Zero;
+        for pair in self.digits.iter().zip_longest(rhs.digits) {
+            let (result, carry) = match pair {
+                EitherOrBoth::Both(lhs, rhs) => *lhs + rhs,
+                EitherOrBoth::Left(lhs) => (*lhs, Digit::Zero),
+                EitherOrBoth::Right(rhs) => (rhs, Digit::Zero),
+            };
+            let (adjusted_from_carry, extra) = result + previous_carry;
+            let (new_carry, nothing) = carry + extra;
+            // this should have given us no excess carry
+            assert!(nothing == Digit::Zero);
+            digits.push(adjusted_from_carry);
+            previous_carry = new_carry;
+        }
+
+        // now all the digits have been added - don't forget the final carry
+        if previous_carry != Digit::Zero {
+            digits.push(previous_carry);
+        }
+
+        Self::Output { digits }
+    }
+}
+
+impl Sum for SnafuNumber {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let mut result = Self {
+            digits: vec![Digit::Zero],
+        };
+        for n in iter {
+            result = result + n;
+        }
+        result
+    }
+}
+
+impl std::fmt::Display for SnafuNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for d in self.digits.iter().rev() {
+            match d {
+                Digit::One => write!(f, "1")?,
+                Digit::Two => write!(f, "2")?,
+                Digit::Zero => write!(f, "0")?,
+                Digit::Minus => write!(f, "-")?,
+                Digit::DoubleMinus => write!(f, "=")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// where in the string things went wrong - an empty string is wrong at the start
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidSnafuDigit {
+    pub position: usize,
+}
+
+impl std::fmt::Display for InvalidSnafuDigit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid SNAFU digit at position {}", self.position)
+    }
+}
+
+impl std::error::Error for InvalidSnafuDigit {}
+
+impl FromStr for SnafuNumber {
+    type Err = InvalidSnafuDigit;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(InvalidSnafuDigit { position: 0 });
+        }
+
+        let mut digits = Vec::new();
+        for (position, c) in s.char_indices().rev() {
+            digits.push(match c {
+                '2' => Digit::Two,
+                '1' => Digit::One,
+                '0' => Digit::Zero,
+                '-' => Digit::Minus,
+                '=' => Digit::DoubleMinus,
+                _ => return Err(InvalidSnafuDigit { position }),
+            });
+        }
+        Ok(Self { digits })
+    }
+}
+
+pub struct Input {}
+
+pub fn input_generator_part1(input: &str) -> Result<Vec<SnafuNumber>, ParseError> {
+    parse::lines(Day25::DAY, input)
+        .map(|l| {
+            l.text().parse().map_err(|e: InvalidSnafuDigit| {
+                l.error_at(e.position, "a SNAFU digit (`2`, `1`, `0`, `-` or `=`)")
+            })
+        })
+        .collect()
+}
+
+pub fn solve_part1(input: &[SnafuNumber]) -> String {
+    input.iter().cloned().sum::<SnafuNumber>().to_string()
+}
+
+// `numbers` SNAFU numbers, up to 20 digits long
+pub fn generate(rng: &mut Rng, numbers: usize) -> String {
+    (0..numbers)
+        .map(|_| {
+            let mut number = rng.choose(&['1', '2']).to_string();
+            for _ in 1..rng.range(1..=20) {
+                number.push(*rng.choose(&['=', '-', '0', '1', '2']));
+            }
+            number.push('\n');
+            number
+        })
+        .collect()
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u8 = 25;
+    const HAS_PART2: bool = false;
+
+    type Part1Input = Vec<SnafuNumber>;
+    type Part2Input = ();
+    type Part1Output = String;
+    type Part2Output = NoAnswer;
+    type Params = NoParams;
+
+    fn parse_part1(input: &str) -> Result<Self::Part1Input, ParseError> {
+        input_generator_part1(input)
+    }
+
+    fn parse_part2(_input: &str) -> Result<Self::Part2Input, ParseError> {
+        Ok(())
+    }
+
+    fn solve_part1(input: &Self::Part1Input, _params: &Self::Params) -> Self::Part1Output {
+        solve_part1(input)
+    }
+
+    fn solve_part2(_input: &Self::Part2Input, _params: &Self::Params) -> Self::Part2Output {
+        NoAnswer
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+aoc_common::example_tests!(Day25);
+
+#[test]
+fn test_day25_adding() {
+    let nine: SnafuNumber = "2-".parse().unwrap();
+    let two_hundred_one: SnafuNumber = "2=01".parse().unwrap();
+    let nine_hundred_six: SnafuNumber = "12111".parse().unwrap();
+
+    let result = nine_hundred_six.clone() + nine_hundred_six + two_hundred_one + nine;
+
+    assert_eq!(result.to_string(), "1=11-2");
+}
+
+#[test]
+fn test_day25_invalid_digit() {
+    assert_eq!(
+        "1=3-".parse::<SnafuNumber>(),
+        Err(InvalidSnafuDigit { position: 2 })
+    );
+
+    let error = input_generator_part1("2=01\n1=3-\n").unwrap_err();
+    assert_eq!((error.line, error.column), (2, 3));
+    assert_eq!(error.found, "`3-`");
+}